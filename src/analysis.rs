@@ -0,0 +1,208 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::differentiation;
+use crate::root_finding;
+use crate::max_finding;
+use crate::global_max::Extremum;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "CURVE SKETCHING".to_string(),
+		links:  calculus::links(14),
+		instructions: "This page analyzes a function over a closed interval, as is done when sketching its graph.  It finds all of the function's zeros, local maxima and minima, and inflection points, and it finds where the function is increasing or decreasing and where it is concave up or down.  In the url bar after <tt>'https://basic-calculus.herokuapp.com/analyze</tt> type the following:<p align=center>&sol;&lt;lower end of the interval&gt;&sol;&lt;upper end of the interval&gt;&sol;&lt;function of <i>x</I>&gt;</tt></p>Features which are narrower than the spacing between the samples (see below) may be missed, as may a zero at which the function does not change sign unless it is also a local extremum.  The function must be defined slightly beyond the ends of the interval.".to_string(),
+		note: format!("{}{}", helper::NOTE1, helper::NOTE2).to_string(),
+		example: "To analyze the function <i>x</i><sup>3</sup> - 3<i>x</i> over the interval from <i>x</i> = -3 to 3, type <tt>/-3/3/x**3-3x</tt> after the current url address.  The zeros should be at <tt>-1.732..., 0, and 1.732...</tt>, the local maximum and minimum (other than those at the endpoints) should be at <tt>(-1, 2)</tt> and <tt>(1, -2)</tt>, and the inflection point should be at <tt>(0, 0)</tt>.".to_string(),
+		algorithm: "The function and its first two derivatives (calculated by finite differences, as on the differentiation page) are sampled at 1000 evenly spaced intervals.  A change in the sign of the function brackets a zero, which is located as on the root-finding page.  A change in the sign of the first derivative brackets a local extremum, which is located as on the max-finding page.  A change in the sign of the second derivative brackets an inflection point, which is located by finding the root of the second derivative.".to_string(),
+		json: "Type '/json' in the url bar immediately after 'analyze' if you would like the result in this format rather than html.  A successful response will contain seven properties. 'a' and 'b' are the ends of the interval, 'zeros' is an array of the function's zeros, 'extrema' is an array of the local extrema (each of which has the properties 'x', 'f', 'kind' (either 'max' or 'min'), and 'endpoint' (a boolean)), 'inflections' is an array of the coordinates ('x' and 'f') of the inflection points, 'monotonicity' is an array of intervals in which the function is either 'increasing' or 'decreasing', and 'concavity' is an array of intervals in which the function is either 'concave up' or 'concave down'.  Each interval has the properties 'from', 'to', and 'behavior'. An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Point {
+	pub x: f64,
+	pub f: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Interval {
+	pub from: f64,
+	pub to: f64,
+	pub behavior: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub a: f64,
+	pub b: f64,
+	pub zeros: Vec<f64>,
+	pub extrema: Vec<Extremum>,
+	pub inflections: Vec<Point>,
+	pub monotonicity: Vec<Interval>,
+	pub concavity: Vec<Interval>,
+}
+
+// Pairs of indices of samples whose values have opposite signs and between which all values vanish
+fn sign_changes(values: &[f64]) -> Vec<(usize, usize)> {
+	let mut pairs = vec![];
+	let mut last = None;
+	for (i, value) in values.iter().enumerate() {
+		if *value == 0. {
+			continue;
+		}
+		if let Some(j) = last {
+			if values[j] * value < 0. {
+				pairs.push((j, i));
+			}
+		}
+		last = Some(i);
+	}
+	pairs
+}
+
+// Divide [a, b] at the given points, and describe each piece by the sign of g at its midpoint.
+fn intervals<F: Fn(f64) -> Result<f64, String>>(g: &F, a: f64, b: f64, points: &[f64], names: [&str; 3]) -> Result<Vec<Interval>, String> {
+	let mut ends = vec![a];
+	ends.extend_from_slice(points);
+	ends.push(b);
+	let mut intervals: Vec<Interval> = vec![];
+	for i in 0..ends.len() - 1 {
+		let g_mid = match g((ends[i] + ends[i + 1]) / 2.) {
+			Ok(g_mid) => g_mid,
+			Err(message) => return Err(message),
+		};
+		let behavior = (if g_mid > 0. {names[0]} else if g_mid < 0. {names[1]} else {names[2]}).to_string();
+		let n_intervals = intervals.len();
+		// Merge neighboring intervals which behave in the same way.
+		if n_intervals > 0 && intervals[n_intervals - 1].behavior == behavior {
+			intervals[n_intervals - 1].to = ends[i + 1];
+		} else {
+			intervals.push(Interval {from: ends[i], to: ends[i + 1], behavior});
+		}
+	}
+	Ok(intervals)
+}
+
+pub fn raw(a_str: &RawStr, b_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
+	let n = 1000;
+	let epsilon = (10_f64).powf(-12.);
+	let a = match helper::parse_expression(a_str.to_string()) {
+		Ok(a) => a,
+		Err(message) => return Err(message),
+	};
+	let b = match helper::parse_expression(b_str.to_string()) {
+		Ok(b) => b,
+		Err(message) => return Err(message),
+	};
+	if b <= a {
+		return Err(format!("Error: the upper end of the interval ({}) must exceed its lower end ({}).", b, a));
+	}
+	let f = |x: f64| helper::function1(input_str.to_string(), x);
+	// jth derivative of f
+	let deriv = |x: f64, j: usize| match differentiation::derivs(&f, x) {
+		Ok((_, derivs)) => Ok(derivs[j]),
+		Err(message) => Err(message),
+	};
+	let mut xs = vec![];
+	let mut fs = vec![];
+	let mut d1s = vec![];
+	let mut d2s = vec![];
+	for i in 0..=n {
+		let x = a + (b - a) * (i as f64) / (n as f64);
+		let derivs = match differentiation::derivs(&f, x) {
+			Ok((true, derivs)) => derivs,
+			Ok((false, _)) => return Err(format!("Error: the function is not defined at x = {}.", x)),
+			Err(message) => return Err(message),
+		};
+		xs.push(x);
+		fs.push(derivs[0]);
+		d1s.push(derivs[1]);
+		d2s.push(derivs[2]);
+	}
+	let mut zeros = vec![];
+	for (i, f_i) in fs.iter().enumerate() {
+		// A vanishing sample is a zero only if the function does not change sign there.
+		if *f_i == 0. && (i == 0 || i == n || fs[i - 1] * fs[i + 1] >= 0.) {
+			zeros.push(xs[i]);
+		}
+	}
+	for (j, i) in sign_changes(&fs) {
+		let (x, _) = match root_finding::bracketed(&f, xs[j], xs[i], fs[j], fs[i], epsilon, 100) {
+			Ok(result) => result,
+			Err(message) => return Err(message),
+		};
+		let f_x = match f(x) {
+			Ok(f_x) => f_x,
+			Err(message) => return Err(message),
+		};
+		// A sign change across a discontinuity is not a zero.
+		if f_x.abs() <= fs[j].abs().min(fs[i].abs()) {
+			zeros.push(x);
+		}
+	}
+	// An endpoint where the derivative vanishes takes its kind from the first sample which differs from it.  A constant function has a minimum at its lower end and a maximum at its upper one, as on the global-max page.
+	let endpoint_kind = |slope: f64, f: f64, other: Option<&f64>, constant: &str| {
+		let kind = if slope != 0. {
+			if slope < 0. {"max"} else {"min"}
+		} else {
+			match other {
+				Some(other) => if *other < f {"max"} else {"min"},
+				None => constant,
+			}
+		};
+		kind.to_string()
+	};
+	let mut extrema = vec![];
+	extrema.push(Extremum {x: a, f: fs[0], kind: endpoint_kind(d1s[0], fs[0], fs.iter().find(|f| **f != fs[0]), "min"), endpoint: true});
+	for (j, i) in sign_changes(&d1s) {
+		let sign = if d1s[j] > 0. {1.} else {-1.};
+		// Brent's method finds a minimum, so a maximum of f is found as the minimum of -f.
+		let g = |x: f64| f(x).map(|f| -sign * f);
+		let x_mid = (xs[j] + xs[i]) / 2.;
+		let g_mid = match g(x_mid) {
+			Ok(g_mid) => g_mid,
+			Err(message) => return Err(message),
+		};
+		let (x, g, _) = match max_finding::brent(&g, xs[j], xs[i], x_mid, g_mid) {
+			Ok(result) => result,
+			Err(message) => return Err(message),
+		};
+		// A local extremum which touches the axis is also a zero.
+		if g.abs() <= epsilon && !zeros.iter().any(|zero| (zero - x).abs() <= (xs[1] - xs[0])) {
+			zeros.push(x);
+		}
+		extrema.push(Extremum {x, f: -sign * g, kind: (if sign > 0. {"max"} else {"min"}).to_string(), endpoint: false});
+	}
+	extrema.push(Extremum {x: b, f: fs[n], kind: endpoint_kind(-d1s[n], fs[n], fs.iter().rev().find(|f| **f != fs[n]), "max"), endpoint: true});
+	let mut inflections = vec![];
+	let d2 = |x: f64| deriv(x, 2);
+	for (j, i) in sign_changes(&d2s) {
+		// Second derivatives by finite differences are only accurate to about 10^-9 or so.
+		let (x, _) = match root_finding::bracketed(&d2, xs[j], xs[i], d2s[j], d2s[i], epsilon.sqrt() / 1000., 100) {
+			Ok(result) => result,
+			Err(message) => return Err(message),
+		};
+		let (d2_x, f_x) = match (d2(x), f(x)) {
+			(Ok(d2_x), Ok(f_x)) => (d2_x, f_x),
+			(Err(message), _) | (_, Err(message)) => return Err(message),
+		};
+		if d2_x.abs() <= d2s[j].abs().min(d2s[i].abs()) {
+			inflections.push(Point {x, f: f_x});
+		}
+	}
+	zeros.sort_by(|a, b| a.partial_cmp(b).unwrap());
+	let critical: Vec<f64> = extrema.iter().filter(|extremum| !extremum.endpoint).map(|extremum| extremum.x).collect();
+	let monotonicity = match intervals(&|x| deriv(x, 1), a, b, &critical, ["increasing", "decreasing", "constant"]) {
+		Ok(monotonicity) => monotonicity,
+		Err(message) => return Err(message),
+	};
+	let inflection_xs: Vec<f64> = inflections.iter().map(|point| point.x).collect();
+	let concavity = match intervals(&d2, a, b, &inflection_xs, ["concave up", "concave down", "linear"]) {
+		Ok(concavity) => concavity,
+		Err(message) => return Err(message),
+	};
+	Ok(Results {a, b, zeros, extrema, inflections, monotonicity, concavity})
+}
@@ -0,0 +1,231 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::integrators;
+use crate::root_finding;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "BOUNDARY-VALUE PROBLEMS".to_string(),
+		links:  calculus::links(16),
+		instructions: "This page solves a differential equation of the form <i>d</I><sup>2</sup><i>x/dt</i><sup>2</sup> = function of <I>x</I>, of <I>dx/dt</I> (= '<i>v</I>'), and of <I>t</I>, with specified values of <i>x</i> at both ends of an interval of <i>t</i>, which starts at <i>t</i> = 0 by default.  In the url bar after <tt>'https://basic-calculus.herokuapp.com/bvp</tt> type the following:<p align=center>&sol;&lt;value of <i>x</I> at the start of the interval&gt;&sol;&lt;value of <i>x</I> at the end of the interval&gt;&sol;&lt;final value of <i>t</I>&gt;&sol;&lt;number of intervals&gt;&sol;&lt;function of <i>x</I>, <i>v</I>, and <i>t</I>&gt;</tt></p>The interval may instead start at a nonzero time by appending for instance <tt>?ti=1</tt> to the url.  By default the shooting method is used.  For a problem for which this fails (eg because the solution of the initial-value problem diverges for some initial values of <i>v</i>), append <tt>?method=finite-difference</tt> instead.  (If more than one option such as these is appended to the url, each after the first must be preceded by <tt>&</tt> rather than <tt>?</tt>.)".to_string(),
+		note: format!("{}{}", helper::NOTE1, helper::NOTE2).to_string(),
+		example: "To solve the equation d<sup>2</sup>x/dt<sup>2</sup> = -x with the boundary conditions that x(0) = 0 and x(1) = 1 using 10 intervals, type <tt>/0/1/1/10/-x</tt> after /bvp in the url above.  The initial value of v should be 1/sin(1) = 1.1883..., and x(0.5) should be 0.5697....  Appending <tt>?method=finite-difference</tt> should give the less accurate value x(0.5) = 0.5698....".to_string(),
+		algorithm: "In the shooting method the initial value of <i>v</i> is adjusted until the solution of the initial-value problem (found by the rk45 method of the 2nd-order ODE page, with a relative tolerance of 10<sup>-10</sup>) satisfies the boundary condition at the end of the interval, which is done by bracketing and then finding the root of the error in that condition, as on the root-finding page.  In the finite-difference method the derivatives at each of the points which divide the interval are replaced by central differences, and the resulting nonlinear equations for the values of <i>x</i> are solved by Newton's method, whose tridiagonal Jacobian matrix is calculated by central differences.  The error of the latter method decreases as the square of the size of the intervals.".to_string(),
+		json: "Type '/json' in the url bar immediately after 'bvp' if you would like the result in this format rather than html.  A successful response will contain eleven properties. 'ti' and 'tf' are the ends of the interval, 'xi' and 'xf' are the values of <i>x</i> there, 'nt' is the number of intervals, 'method' is either 'shooting' or 'finite-difference', 'ts' is the array of times which divide the interval, 'xs' and 'vs' are the arrays of <i>x</i> and <i>v</i> at those times, 'steps' is the number of iterations (of the root-finder or of Newton's method) which were required, and 'epsilon' is the accuracy to which these converged. An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub ti: f64,
+	pub tf: f64,
+	pub xi: f64,
+	pub xf: f64,
+	pub nt: i32,
+	pub method: String,
+	pub ts: Vec<f64>,
+	pub xs: Vec<f64>,
+	pub vs: Vec<f64>,
+	pub steps: i32,
+	pub epsilon: f64,
+}
+
+// Solve a tridiagonal linear system, whose three diagonals are below, on, and above the main one.
+fn tridiagonal(lower: &[f64], diagonal: &[f64], upper: &[f64], b: &[f64]) -> Result<Vec<f64>, String> {
+	let n = b.len();
+	let mut diagonal = diagonal.to_vec();
+	let mut b = b.to_vec();
+	for i in 1..n {
+		if diagonal[i - 1] == 0. {
+			return Err("Error: the matrix of this linear system is singular.".to_string());
+		}
+		let factor = lower[i] / diagonal[i - 1];
+		diagonal[i] -= factor * upper[i - 1];
+		b[i] -= factor * b[i - 1];
+	}
+	if diagonal[n - 1] == 0. {
+		return Err("Error: the matrix of this linear system is singular.".to_string());
+	}
+	let mut x = vec![0.; n];
+	x[n - 1] = b[n - 1] / diagonal[n - 1];
+	for i in (0..n - 1).rev() {
+		x[i] = (b[i] - upper[i] * x[i + 1]) / diagonal[i];
+	}
+	Ok(x)
+}
+
+fn shooting<F: Fn(f64, &[f64]) -> Result<Vec<f64>, String>>(
+	f: &F,
+	ti: f64,
+	tf: f64,
+	xi: f64,
+	xf: f64,
+	nt: i32,
+	epsilon: f64,
+) -> Result<(Vec<f64>, Vec<f64>, Vec<f64>, i32), String> {
+	let bracket_steps_max = 30;
+	let settings = match integrators::settings(Some("rk45"), Some("1e-10"), Some("1e-12")) {
+		Ok(settings) => settings,
+		Err(message) => return Err(message),
+	};
+	let none = integrators::Events::none();
+	// error in the final boundary condition, for a particular initial value of v
+	let g = |vi: f64| match integrators::solve(f, ti, &[xi, vi], tf, 1, &settings, &none) {
+		Ok(solution) => Ok(solution.ys[1][0] - xf),
+		Err(message) => Err(message),
+	};
+	// First, bracket the root, starting from the slope of the straight line between the boundary values.
+	let v_line = (xf - xi) / (tf - ti);
+	let step = 0.1 * v_line.abs().max(1.);
+	let (v0, v2, g0, g2, bracket_steps) = match root_finding::bracket(&g, v_line - step / 2., v_line + step / 2., step, bracket_steps_max) {
+		Ok(Some(result)) => result,
+		Ok(None) => return Err(format!("Unable to find an initial value of v which satisfies the final boundary condition after {} steps.  Try method=finite-difference.", bracket_steps_max)),
+		Err(message) => return Err(message),
+	};
+	// Second, find the root that has been bracketed.
+	let (vi, root_steps) = match root_finding::bracketed(&g, v0, v2, g0, g2, epsilon * xf.abs().max(1.), 100) {
+		Ok(result) => result,
+		Err(message) => return Err(message),
+	};
+	let solution = match integrators::solve(f, ti, &[xi, vi], tf, nt, &settings, &none) {
+		Ok(solution) => solution,
+		Err(message) => return Err(message),
+	};
+	Ok((
+		solution.ts,
+		solution.ys.iter().map(|y| y[0]).collect(),
+		solution.ys.iter().map(|y| y[1]).collect(),
+		bracket_steps + root_steps,
+	))
+}
+
+fn finite_difference<F: Fn(f64, &[f64]) -> Result<Vec<f64>, String>>(
+	f: &F,
+	ti: f64,
+	tf: f64,
+	xi: f64,
+	xf: f64,
+	nt: i32,
+	epsilon: f64,
+) -> Result<(Vec<f64>, Vec<f64>, Vec<f64>, i32), String> {
+	let steps_max = 50;
+	let n = nt as usize;
+	if n < 2 {
+		return Err("Error: the finite-difference method requires at least two intervals.".to_string());
+	}
+	let h = (tf - ti) / (nt as f64);
+	let ts: Vec<f64> = (0..=n).map(|i| if i == n {tf} else {ti + (i as f64) * h}).collect();
+	// Start from the straight line between the boundary values.
+	let mut xs: Vec<f64> = ts.iter().map(|t| xi + (xf - xi) * (t - ti) / (tf - ti)).collect();
+	let accel = |x: f64, v: f64, t: f64| match f(t, &[x, v]) {
+		Ok(a) => Ok(a[1]),
+		Err(message) => Err(message),
+	};
+	let mut steps = 0;
+	loop {
+		if steps >= steps_max {
+			return Err(format!("Unable to converge within {} steps.", steps_max));
+		}
+		steps += 1;
+		// residuals of the discretized equation at the interior points, and their derivatives
+		let (mut lower, mut diagonal, mut upper, mut residuals) = (vec![0.; n - 1], vec![0.; n - 1], vec![0.; n - 1], vec![0.; n - 1]);
+		for i in 1..n {
+			let (x, v, t) = (xs[i], (xs[i + 1] - xs[i - 1]) / 2. / h, ts[i]);
+			let dx = 1e-6 * x.abs().max(1.);
+			let dv = 1e-6 * v.abs().max(1.);
+			let mut a = vec![];
+			for (x, v) in [(x, v), (x + dx, v), (x - dx, v), (x, v + dv), (x, v - dv)] {
+				a.push(match accel(x, v, t) {
+					Ok(a) => a,
+					Err(message) => return Err(message),
+				});
+			}
+			let (a_x, a_v) = ((a[1] - a[2]) / 2. / dx, (a[3] - a[4]) / 2. / dv);
+			residuals[i - 1] = xs[i + 1] - 2. * xs[i] + xs[i - 1] - h * h * a[0];
+			lower[i - 1] = 1. + h * a_v / 2.;
+			diagonal[i - 1] = -2. - h * h * a_x;
+			upper[i - 1] = 1. - h * a_v / 2.;
+		}
+		let delta = match tridiagonal(&lower, &diagonal, &upper, &residuals) {
+			Ok(delta) => delta,
+			Err(message) => return Err(message),
+		};
+		let size = delta.iter().fold(0., |max: f64, d| max.max(d.abs()));
+		for i in 1..n {
+			xs[i] -= delta[i - 1];
+		}
+		if !size.is_finite() {
+			return Err("Error: Newton's method diverged.".to_string());
+		}
+		if size <= epsilon * (1. + xs.iter().fold(0., |max: f64, x| max.max(x.abs()))) {
+			break;
+		}
+	}
+	// Velocities are found by central differences inside the interval and by 2nd-order one-sided differences at its ends.
+	let mut vs = vec![(-3. * xs[0] + 4. * xs[1] - xs[2]) / 2. / h];
+	for i in 1..n {
+		vs.push((xs[i + 1] - xs[i - 1]) / 2. / h);
+	}
+	vs.push((3. * xs[n] - 4. * xs[n - 1] + xs[n - 2]) / 2. / h);
+	Ok((ts, xs, vs, steps))
+}
+
+pub fn raw(
+	xi_str: &RawStr,
+	xf_str: &RawStr,
+	tf_str: &RawStr,
+	nt_str: &RawStr,
+	input_str: &RawStr,
+	ti_str: Option<&str>,
+	method: Option<&str>,
+) -> Result<Results, String> {
+	let epsilon = (10_f64).powf(-10.);
+	let method = method.unwrap_or("shooting").to_lowercase();
+	let ti = match integrators::initial_time(ti_str) {
+		Ok(ti) => ti,
+		Err(message) => return Err(message),
+	};
+	let xi = match helper::parse_expression(xi_str.to_string()) {
+		Ok(xi) => xi,
+		Err(message) => return Err(message),
+	};
+	let xf = match helper::parse_expression(xf_str.to_string()) {
+		Ok(xf) => xf,
+		Err(message) => return Err(message),
+	};
+	let tf = match helper::parse_expression(tf_str.to_string()) {
+		Ok(tf) => tf,
+		Err(message) => return Err(message),
+	};
+	if tf == ti {
+		return Err("Error: the two ends of the interval must differ.".to_string());
+	}
+	let nt = match helper::parse_expression(nt_str.to_string()) {
+		Ok(nt) => {
+			if nt.round() != nt {
+				return Err(format!("{} is not an integer.", nt));
+			} else if nt <= 0. {
+				return Err("Number of intervals must be positive.".to_string());
+			}
+			nt as i32
+		},
+		Err(message) => return Err(message),
+	};
+	let f = |t: f64, y: &[f64]| match helper::function3(input_str.to_string(), y[0], t, y[1]) {
+		Ok(a) => Ok(vec![y[1], a]),
+		Err(message) => Err(message),
+	};
+	let (ts, xs, vs, steps) = match match method.as_str() {
+		"shooting" => shooting(&f, ti, tf, xi, xf, nt, epsilon),
+		"finite-difference" => finite_difference(&f, ti, tf, xi, xf, nt, epsilon),
+		_ => return Err(format!("Error: the method must be either 'shooting' or 'finite-difference', not '{}'.", method)),
+	} {
+		Ok(result) => result,
+		Err(message) => return Err(message),
+	};
+	Ok(Results {ti, tf, xi, xf, nt, method, ts, xs, vs, steps, epsilon})
+}
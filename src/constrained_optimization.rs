@@ -0,0 +1,256 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::optimization;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "CONSTRAINED OPTIMIZATION".to_string(),
+		links:  calculus::links(12),
+		instructions: "This page finds a local maximum or minimum of a function of several variables, subject to constraints which may be equalities and/or inequalities.  In the url bar after <tt>'https://basic-calculus.herokuapp.com/constrained-optimization</tt> type the following:<p align=center>&sol;&lt;comma-separated names of the variables&gt;&sol;&lt;comma-separated values at which to start the search&gt;&sol;&lt;comma-separated constraints&gt;&sol;&lt;function of the variables&gt;</tt></p>Each constraint must contain one of the symbols <tt>&lt;=</tt>, <tt>&gt;=</tt>, or <tt>=</tt> (or <tt>eq</tt>), with a function of the variables on either side.  (<tt>&lt;</tt> and <tt>&gt;</tt> are treated like <tt>&lt;=</tt> and <tt>&gt;=</tt>.)  If there are no constraints other than bounds (see below), type <tt>none</tt> for the constraints.  By default a minimum is found.  To find a maximum, append <tt>?mode=max</tt> to the url.  Lower and/or upper bounds for the variables may be specified by appending comma-separated lists such as <tt>lower=0,0</tt> and/or <tt>upper=1,</tt> to the url, an empty item meaning that the variable is unbounded.  The starting point need not satisfy the constraints.".to_string(),
+		note: format!("{}{}", helper::NOTE1, helper::NOTE2).to_string(),
+		example: "To find the maximum of <i>xy</i> subject to the constraints that <i>x</i> + <i>y</i> &le; 10 and <i>x</i> &le; 3 and that neither variable be negative, while starting the search at (<i>x</i>, <i>y</i>) = (1, 1), type <tt>/x,y/1,1/x+y<=10,x<=3/xy?mode=max&lower=0,0</tt> after the current url address.  The result for this should be that the maximum value 21 occurs at (3, 7), where the first two constraints are active, with Lagrange multipliers of 3 and 4, respectively.".to_string(),
+		algorithm: "the augmented-Lagrangian method, in which the BFGS method (see the multivariable-optimization page) minimizes the sum of the function and quadratic penalties for the constraints, after which the Lagrange multipliers are updated.  The penalty parameter grows whenever the constraints' violation does not decrease enough.".to_string(),
+		json: "Type '/json' in the url bar immediately after 'constrained-optimization' if you would like the result in this format rather than html.  A successful response will contain nine properties. 'mode' is either 'min' or 'max', 'vars' is the list of names of the variables, 'xi' is the point where the search starts, 'x' is where the search ends, 'f' is the function value there, 'constraints' is an array describing each constraint (including bounds), 'violation' is the largest amount by which any constraint is violated, 'steps' is the number of times that the Lagrange multipliers are updated, and 'epsilon' is the accuracy to which the constraints must be satisfied.  Each constraint has four properties: 'constraint' is the constraint itself, 'value' is the difference between its two sides (which is negative if an inequality is satisfied but inactive), 'active' is a boolean which indicates whether or not the constraint holds as an equality, and 'multiplier' is its Lagrange multiplier (which is nonnegative for an inequality, and whose magnitude is the rate at which the optimum changes as the constraint is loosened). An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Constraint {
+	pub constraint: String,
+	pub value: f64,
+	pub active: bool,
+	pub multiplier: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub mode: String,
+	pub vars: Vec<String>,
+	pub xi: Vec<f64>,
+	pub x: Vec<f64>,
+	pub f: f64,
+	pub constraints: Vec<Constraint>,
+	pub violation: f64,
+	pub steps: i32,
+	pub epsilon: f64,
+}
+
+// Each constraint is stored as a function c which must either vanish (equality) or be nonpositive (inequality).
+struct Condition {
+	text: String,
+	c: String,
+	equality: bool,
+}
+
+fn parse_constraint(constraint_str: &str, vars: &[String]) -> Result<Condition, String> {
+	let mut text = constraint_str.to_lowercase();
+	for (code, symbol) in [("%3c", "<"), ("%3e", ">"), ("%3d", "="), ("%5e", "^"), ("%20", "")] {
+		text = str::replace(&text, code, symbol);
+	}
+	for (symbol, sign) in [("<=", 1.), (">=", -1.), ("<", 1.), (">", -1.)] {
+		let sides: Vec<&str> = text.split(symbol).collect();
+		if sides.len() == 1 {
+			continue;
+		}
+		if sides.len() > 2 || sides[0].is_empty() || sides[1].is_empty() {
+			return Err(format!("Error: {} is not a legal inequality.", text));
+		}
+		let c = if sign > 0. {
+			format!("({})-({})", sides[0], sides[1])
+		} else {
+			format!("({})-({})", sides[1], sides[0])
+		};
+		return Ok(Condition {text: text.clone(), c, equality: false});
+	}
+	let c = match helper::equation(&text, vars) {
+		Ok(c) => c,
+		Err(message) => return Err(message),
+	};
+	if c == text {
+		return Err(format!("Error: the constraint {} contains neither an equals sign nor an inequality.", text));
+	}
+	Ok(Condition {text, c, equality: true})
+}
+
+// Bounds are converted to inequality constraints.
+fn parse_bounds(bounds_str: &str, vars: &[String], lower: bool) -> Result<Vec<Condition>, String> {
+	let bounds = helper::parse_list(bounds_str);
+	if bounds.len() != vars.len() {
+		return Err(format!("Error: there are {} variables but {} bounds in {}.", vars.len(), bounds.len(), bounds_str));
+	}
+	let mut conditions = vec![];
+	for (var, bound_str) in vars.iter().zip(bounds) {
+		if bound_str.is_empty() {
+			continue;
+		}
+		let bound = match helper::parse_expression(bound_str) {
+			Ok(bound) => bound,
+			Err(message) => return Err(message),
+		};
+		conditions.push(if lower {
+			Condition {text: format!("{}>={}", var, bound), c: format!("({})-{}", bound, var), equality: false}
+		} else {
+			Condition {text: format!("{}<={}", var, bound), c: format!("{}-({})", var, bound), equality: false}
+		});
+	}
+	Ok(conditions)
+}
+
+pub fn raw(
+	vars_str: &RawStr,
+	xi_str: &RawStr,
+	constraints_str: &RawStr,
+	input_str: &RawStr,
+	mode: &str,
+	lower_str: Option<&str>,
+	upper_str: Option<&str>,
+) -> Result<Results, String> {
+	let epsilon = (10_f64).powf(-8.);
+	let steps_max = 50;
+	// Finding a maximum of f is equivalent to finding a minimum of -f.
+	let sign = match mode {
+		"max" => -1.,
+		"min" => 1.,
+		_ => return Err(format!("Error: the mode must be either 'max' or 'min', not '{}'.", mode)),
+	};
+	let vars = match helper::parse_names(vars_str) {
+		Ok(vars) => vars,
+		Err(message) => return Err(message),
+	};
+	let mut xi = vec![];
+	for x_str in helper::parse_list(xi_str) {
+		xi.push(match helper::parse_expression(x_str) {
+			Ok(x) => x,
+			Err(message) => return Err(message),
+		});
+	}
+	if xi.len() != vars.len() {
+		return Err(format!("Error: there are {} variables but {} starting values.", vars.len(), xi.len()));
+	}
+	let mut conditions = vec![];
+	if constraints_str.to_lowercase() != "none" {
+		for constraint_str in helper::parse_list(constraints_str) {
+			conditions.push(match parse_constraint(&constraint_str, &vars) {
+				Ok(condition) => condition,
+				Err(message) => return Err(message),
+			});
+		}
+	}
+	for (bounds_str, lower) in [(lower_str, true), (upper_str, false)] {
+		if let Some(bounds_str) = bounds_str {
+			conditions.append(&mut match parse_bounds(bounds_str, &vars, lower) {
+				Ok(bounds) => bounds,
+				Err(message) => return Err(message),
+			});
+		}
+	}
+	let f = |x: &[f64]| helper::function_n(input_str, &vars, x);
+	let cs = |x: &[f64]| -> Result<Vec<f64>, String> {
+		let mut cs = vec![];
+		for condition in &conditions {
+			cs.push(match helper::function_n(&condition.c, &vars, x) {
+				Ok(c) => c,
+				Err(message) => return Err(message),
+			});
+		}
+		Ok(cs)
+	};
+	let violation = |cs: &[f64]| conditions.iter().zip(cs).map(|(condition, c)| {
+		if condition.equality {c.abs()} else {c.max(0.)}
+	}).fold(0., f64::max);
+	let mut x = xi.clone();
+	let mut multipliers = vec![0.; conditions.len()];
+	let mut rho = 10.;
+	let mut c_values = match cs(&x) {
+		Ok(c_values) => c_values,
+		Err(message) => return Err(message),
+	};
+	let mut max_violation = violation(&c_values);
+	let mut steps = 0;
+	loop {
+		// the augmented Lagrangian, for the current multipliers and penalty parameter
+		let lagrangian = |x: &[f64]| -> Result<f64, String> {
+			let mut sum = match f(x) {
+				Ok(f) => sign * f,
+				Err(message) => return Err(message),
+			};
+			let c_values = match cs(x) {
+				Ok(c_values) => c_values,
+				Err(message) => return Err(message),
+			};
+			for (i, condition) in conditions.iter().enumerate() {
+				sum += if condition.equality {
+					multipliers[i] * c_values[i] + rho / 2. * c_values[i] * c_values[i]
+				} else {
+					let shifted = (multipliers[i] / rho + c_values[i]).max(0.);
+					rho / 2. * (shifted * shifted - (multipliers[i] / rho).powi(2))
+				};
+			}
+			Ok(sum)
+		};
+		// Rounding errors in the augmented Lagrangian grow with the penalty parameter, and so does the tolerance of its minimization.
+		let history = match optimization::bfgs(&lagrangian, &x, (epsilon * epsilon * rho).min(epsilon)) {
+			Ok(history) => history,
+			Err(message) => return Err(message),
+		};
+		let x_new = history[history.len() - 1].x.clone();
+		c_values = match cs(&x_new) {
+			Ok(c_values) => c_values,
+			Err(message) => return Err(message),
+		};
+		let violation_new = violation(&c_values);
+		let mut change = 0.;
+		for (i, condition) in conditions.iter().enumerate() {
+			let multiplier = if condition.equality {
+				multipliers[i] + rho * c_values[i]
+			} else {
+				(multipliers[i] + rho * c_values[i]).max(0.)
+			};
+			change = f64::max(change, (multiplier - multipliers[i]).abs());
+			multipliers[i] = multiplier;
+		}
+		let size = x_new.iter().zip(&x).map(|(a, b)| (a - b).abs()).fold(0., f64::max);
+		x = x_new;
+		steps += 1;
+		if violation_new <= epsilon && (size <= epsilon.sqrt() * (1. + x.iter().map(|xj| xj.abs()).fold(0., f64::max)) || change <= epsilon) {
+			max_violation = violation_new;
+			break;
+		}
+		if steps >= steps_max {
+			return Err(format!("Unable to satisfy the constraints to within {} after {} steps.  The largest violation is {}.", epsilon, steps_max, violation_new));
+		}
+		// Increase the penalty if the constraints' violation has not decreased enough.
+		if violation_new > max_violation / 4. {
+			rho *= 10.;
+		}
+		max_violation = violation_new;
+	}
+	let fx = match f(&x) {
+		Ok(fx) => fx,
+		Err(message) => return Err(message),
+	};
+	if !fx.is_finite() {
+		return Err(format!("Error: the function seems to be unbounded in the region allowed by the constraints, because its value is {} at {:?}.", fx, x));
+	}
+	let constraints = conditions.iter().enumerate().map(|(i, condition)| Constraint {
+		constraint: condition.text.clone(),
+		value: c_values[i],
+		active: condition.equality || c_values[i] > -epsilon.sqrt() || multipliers[i] > 0.,
+		multiplier: multipliers[i],
+	}).collect();
+	Ok(Results {
+		mode: mode.to_string(),
+		vars,
+		xi,
+		x,
+		f: fx,
+		constraints,
+		violation: max_violation,
+		steps,
+		epsilon,
+	})
+}
@@ -0,0 +1,199 @@
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::linear_algebra;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "CURVE FITTING".to_string(),
+		links:  calculus::links(13),
+		instructions: "This page fits a model function to data points (<i>x</i>, <i>y</i>) by adjusting the model's parameters so as to minimize chi-squared, the sum of the squares of the differences between the data and the model (each divided by the uncertainty of the data point, if known).  Rather than typing in the url bar, send a POST request to <tt>'https://basic-calculus.herokuapp.com/curve-fitting</tt> whose body is a json object with the following properties:<ul><li>'model': function of <i>x</i> and of the parameters</li><li>'params': array of the names of the parameters</li><li>'initial' (optional): array of the parameters' values at which to start the search (all of which are 1 by default)</li><li>'x': array of the data's <i>x</i>-values</li><li>'y': array of the data's <i>y</i>-values</li><li>'sigma' (optional): array of the uncertainties of the <i>y</i>-values</li></ul>Each parameter's name must start with a letter and may contain letters, digits, and underscores.  Because the model is not part of a url, the usual division symbol (<tt>/</tt>) may be used.".to_string(),
+		note: "Unlike the model, the values in the arrays must be numbers rather than expressions.".to_string(),
+		example: "To fit the model <i>a</i> exp(-<i>bx</i>) + <i>c</i> to four data points, type <tt>curl -X POST -d '{\"model\": \"a*exp(-b*x)+c\", \"params\": [\"a\", \"b\", \"c\"], \"x\": [0, 1, 2, 3], \"y\": [5.0, 3.2, 2.3, 1.7]}' https://basic-calculus.herokuapp.com/curve-fitting</tt> in a terminal.  The fitted values of the parameters should be <tt>3.93..., 0.595..., and 1.06...</tt>".to_string(),
+		algorithm: "the Levenberg-Marquardt method, with the Jacobian matrix calculated by central differences".to_string(),
+		json: "The response is always json.  A successful response will contain nine properties. 'params' is the array of the parameters' names, 'values' and 'uncertainties' are arrays of their fitted values and standard errors, 'covariance' is the parameters' covariance matrix, 'chi2' is the minimized value of chi-squared, 'dof' is the number of degrees of freedom (the number of data points minus the number of parameters), 'residuals' is the array of differences between the data's and the model's <i>y</i>-values, 'steps' is the number of iterations required, and 'epsilon' is the relative accuracy to which chi-squared is converged.  If 'sigma' is not provided, the uncertainties are estimated from the scatter of the data, ie the covariance matrix is multiplied by chi2/dof. An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Data {
+	pub model: String,
+	pub params: Vec<String>,
+	pub initial: Option<Vec<f64>>,
+	pub x: Vec<f64>,
+	pub y: Vec<f64>,
+	pub sigma: Option<Vec<f64>>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub params: Vec<String>,
+	pub values: Vec<f64>,
+	pub uncertainties: Vec<f64>,
+	pub covariance: Vec<Vec<f64>>,
+	pub chi2: f64,
+	pub dof: i32,
+	pub residuals: Vec<f64>,
+	pub steps: i32,
+	pub epsilon: f64,
+}
+
+pub fn raw(body: &str) -> Result<Results, String> {
+	let epsilon = (10_f64).powf(-12.);
+	let steps_max = 200;
+	let data: Data = match serde_json::from_str(body) {
+		Ok(data) => data,
+		Err(message) => return Err(format!("Error: could not parse the json: {}", message)),
+	};
+	let params = match helper::parse_names(&data.params.join(",")) {
+		Ok(params) => params,
+		Err(message) => return Err(message),
+	};
+	if params.contains(&"x".to_string()) {
+		return Err("Error: 'x' is the independent variable, so it cannot be the name of a parameter.".to_string());
+	}
+	let m = params.len();
+	let n = data.x.len();
+	// Without uncertainties, all points are weighted equally.
+	let weighted = data.sigma.is_some();
+	let sigma = data.sigma.unwrap_or(vec![1.; n]);
+	if data.y.len() != n || sigma.len() != n {
+		return Err(format!("Error: there are {} x-values, {} y-values, and {} uncertainties, but these numbers must be equal.", n, data.y.len(), sigma.len()));
+	}
+	if sigma.iter().any(|s| *s <= 0.) {
+		return Err("Error: each uncertainty must be positive.".to_string());
+	}
+	if n <= m {
+		return Err(format!("Error: the number of data points ({}) must exceed the number of parameters ({}).", n, m));
+	}
+	let mut values = data.initial.unwrap_or(vec![1.; m]);
+	if values.len() != m {
+		return Err(format!("Error: there are {} parameters but {} initial values.", m, values.len()));
+	}
+	let model = str::replace(&data.model, " ", "");
+	let mut names = params.clone();
+	names.push("x".to_string());
+	// weighted residuals (y - model) / sigma
+	let residuals = |values: &[f64]| -> Result<Vec<f64>, String> {
+		let mut rs = vec![];
+		for i in 0..n {
+			let mut point = values.to_vec();
+			point.push(data.x[i]);
+			rs.push(match helper::function_n(&model, &names, &point) {
+				Ok(f) => (data.y[i] - f) / sigma[i],
+				Err(message) => return Err(format!("Error evaluating the model at x = {}: {}", data.x[i], message)),
+			});
+		}
+		Ok(rs)
+	};
+	// central-difference approximation of the Jacobian of the weighted model
+	let jacobian = |values: &[f64]| -> Result<Vec<Vec<f64>>, String> {
+		let mut jac = vec![vec![0.; m]; n];
+		for j in 0..m {
+			let dp = 1e-6 * values[j].abs().max(1.);
+			let mut plus = values.to_vec();
+			let mut minus = values.to_vec();
+			plus[j] += dp;
+			minus[j] -= dp;
+			let rs_plus = match residuals(&plus) {
+				Ok(rs) => rs,
+				Err(message) => return Err(message),
+			};
+			let rs_minus = match residuals(&minus) {
+				Ok(rs) => rs,
+				Err(message) => return Err(message),
+			};
+			for i in 0..n {
+				jac[i][j] = (rs_minus[i] - rs_plus[i]) / 2. / dp;
+			}
+		}
+		Ok(jac)
+	};
+	// J^T J, for the weighted model
+	let normal = |jac: &[Vec<f64>]| -> Vec<Vec<f64>> {
+		(0..m).map(|j| (0..m).map(|k| (0..n).map(|i| jac[i][j] * jac[i][k]).sum()).collect()).collect()
+	};
+	let mut rs = match residuals(&values) {
+		Ok(rs) => rs,
+		Err(message) => return Err(message),
+	};
+	let mut chi2 = linear_algebra::dot(&rs, &rs);
+	let mut lambda = 1e-3;
+	let mut steps = 0;
+	loop {
+		if steps >= steps_max {
+			return Err(format!("Unable to converge within {} steps.", steps_max));
+		}
+		steps += 1;
+		let jac = match jacobian(&values) {
+			Ok(jac) => jac,
+			Err(message) => return Err(message),
+		};
+		let a = normal(&jac);
+		let g: Vec<f64> = (0..m).map(|j| (0..n).map(|i| jac[i][j] * rs[i]).sum()).collect();
+		// Increase the damping until chi-squared decreases.
+		let mut improved = false;
+		let mut converged = false;
+		while lambda < 1e10 {
+			let mut damped = a.clone();
+			for j in 0..m {
+				damped[j][j] *= 1. + lambda;
+			}
+			let delta = match linear_algebra::solve(&damped, &g) {
+				Ok(delta) => delta,
+				Err(_) => {
+					lambda *= 10.;
+					continue;
+				},
+			};
+			let trial: Vec<f64> = values.iter().zip(&delta).map(|(p, d)| p + d).collect();
+			// Allow the search to step back from values at which the model cannot be evaluated.
+			if let Ok(rs_new) = residuals(&trial) {
+				let chi2_new = linear_algebra::dot(&rs_new, &rs_new);
+				if chi2_new <= chi2 {
+					converged = chi2 - chi2_new <= epsilon * (chi2_new + epsilon);
+					values = trial;
+					rs = rs_new;
+					chi2 = chi2_new;
+					lambda /= 10.;
+					improved = true;
+					break;
+				}
+			}
+			lambda *= 10.;
+		}
+		// If no step can decrease chi-squared, it is at its minimum.
+		if converged || !improved {
+			break;
+		}
+	}
+	let jac = match jacobian(&values) {
+		Ok(jac) => jac,
+		Err(message) => return Err(message),
+	};
+	let mut covariance = match linear_algebra::invert(&normal(&jac)) {
+		Ok(covariance) => covariance,
+		Err(message) => return Err(format!("{}  The parameters cannot all be determined by these data.", message)),
+	};
+	let dof = (n - m) as i32;
+	if !weighted {
+		for row in &mut covariance {
+			for element in row {
+				*element *= chi2 / (dof as f64);
+			}
+		}
+	}
+	Ok(Results {
+		params,
+		uncertainties: (0..m).map(|j| covariance[j][j].sqrt()).collect(),
+		values,
+		covariance,
+		chi2,
+		dof,
+		residuals: rs.iter().zip(&sigma).map(|(r, s)| r * s).collect(),
+		steps,
+		epsilon,
+	})
+}
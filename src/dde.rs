@@ -0,0 +1,151 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::integrators;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "DELAY DIFFERENTIAL EQUATIONS".to_string(),
+		links:  calculus::links(19),
+		instructions: "This page solves a differential equation of the form <i>dx/dt</I> = function of <I>x</I>, of <i>t</i>, and of the values of <i>x</i> at one or more earlier times <i>t</i> - &tau;, where each 'delay' &tau; is a positive constant.  Instead of an initial value of <i>x</i>, the 'history' of <i>x</i> must be specified, ie its values for all times up to the initial time <i>t</i> = 0 (by default).  In the url bar after <tt>'https://basic-calculus.herokuapp.com/dde</tt> type the following:<p align=center>&sol;&lt;comma-separated delays&gt;&sol;&lt;history of <i>x</i>, ie a function of <i>t</I>&gt;&sol;&lt;final value of <i>t</I>&gt;&sol;&lt;number of time-steps&gt;&sol;&lt;function of <i>x</I>, <i>t</I>, and the delayed values of <i>x</i>&gt;</tt></p>If there is one delay, the delayed value <i>x</i>(<i>t</i> - &tau;) is typed as <tt>xlag</tt>.  If there are more, the delayed values are typed as <tt>xlag1</tt>, <tt>xlag2</tt>, and so on.  The initial time may be changed by appending for instance <tt>?ti=1</tt> to the url.  No time-step may be longer than the shortest delay.".to_string(),
+		note: format!("{}{}", helper::NOTE1, helper::NOTE2).to_string(),
+		example: "To solve the equation dx/dt = -x(t - 1) from t = 0 to t = 3 using 30 time-steps, with the history that x = 1 for t &le; 0, type <tt>/1/1/3/30/-xlag</tt> after /dde in the url above.  The result should be that x(2) = -1/2 and x(3) = -1/6 = -0.1666....  For Hutchinson's equation for a population with a delayed response to crowding, typing <tt>/1/0.5/20/200/1.5x(1-xlag)</tt> should give oscillations whose maxima are separated by 4.1 or so.".to_string(),
+		algorithm: "the 'method of steps', in which each step is taken by the 4th-order Runge-Kutta method (as on the 1st-order ODE page).  The delayed values of <i>x</i> are calculated from the history if they precede the initial time and otherwise by cubic (Hermite) interpolation within the step which contains them, using the values and derivatives at its ends.  Because no step is longer than the shortest delay, these steps have already been taken.".to_string(),
+		json: "Type '/json' in the url bar immediately after 'dde' if you would like the result in this format rather than html.  A successful response will contain seven properties. 'delays' is the array of delays, 'ti' is the initial time, 'xi' is the value of the history there, 'tf' and 'nt' are the final time and number of time-steps, and 'ts' and 'xs' are the arrays of times and of the values of <i>x</i> at those times. An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub delays: Vec<f64>,
+	pub ti: f64,
+	pub xi: f64,
+	pub tf: f64,
+	pub nt: i32,
+	pub ts: Vec<f64>,
+	pub xs: Vec<f64>,
+}
+
+pub fn raw(
+	delays_str: &RawStr,
+	history_str: &RawStr,
+	tf_str: &RawStr,
+	nt_str: &RawStr,
+	input_str: &RawStr,
+	ti_str: Option<&str>,
+) -> Result<Results, String> {
+	let ti = match integrators::initial_time(ti_str) {
+		Ok(ti) => ti,
+		Err(message) => return Err(message),
+	};
+	let mut delays = vec![];
+	for delay_str in helper::parse_list(delays_str) {
+		delays.push(match helper::parse_expression(delay_str) {
+			Ok(delay) => {
+				if delay <= 0. {
+					return Err(format!("Error: each delay must be positive, unlike {}.", delay));
+				}
+				delay
+			},
+			Err(message) => return Err(message),
+		});
+	}
+	let tf = match helper::parse_expression(tf_str.to_string()) {
+		Ok(tf) => tf,
+		Err(message) => return Err(message),
+	};
+	if tf <= ti {
+		return Err(format!("Error: the final time ({}) must exceed the initial time ({}).", tf, ti));
+	}
+	let nt = match helper::parse_expression(nt_str.to_string()) {
+		Ok(nt) => {
+			if nt.round() != nt {
+				return Err(format!("{} is not an integer.", nt));
+			} else if nt <= 0. {
+				return Err("Number of timesteps must be positive.".to_string());
+			}
+			nt as i32
+		},
+		Err(message) => return Err(message),
+	};
+	let dt = (tf - ti) / (nt as f64);
+	let delay_min = delays.iter().fold(f64::INFINITY, |min, delay| min.min(*delay));
+	if dt > delay_min {
+		return Err(format!("Error: the time-step ({}) may not exceed the shortest delay ({}), so at least {} time-steps are needed.", dt, delay_min, ((tf - ti) / delay_min).ceil()));
+	}
+	let history = |t: f64| helper::function_n(history_str, &["t".to_string()], &[t]);
+	let xi = match history(ti) {
+		Ok(xi) => xi,
+		Err(message) => return Err(message),
+	};
+	let mut names = vec!["x".to_string()];
+	if delays.len() == 1 {
+		names.push("xlag".to_string());
+	} else {
+		for j in 1..=delays.len() {
+			names.push(format!("xlag{}", j));
+		}
+	}
+	names.push("t".to_string());
+	let mut steps: Vec<integrators::Step> = vec![];
+	// value of x at time s, which precedes the current step
+	let delayed = |steps: &[integrators::Step], s: f64| -> Result<f64, String> {
+		// (Rounding may put s slightly after the start of the first step.)
+		if s <= ti || steps.is_empty() {
+			return history(s.min(ti));
+		}
+		let k = (((s - ti) / dt).ceil() as usize).clamp(1, steps.len()) - 1;
+		Ok(steps[k].interpolate(s)[0])
+	};
+	let f = |steps: &[integrators::Step], t: f64, x: f64| -> Result<f64, String> {
+		let mut point = vec![x];
+		for delay in &delays {
+			point.push(match delayed(steps, t - delay) {
+				Ok(x) => x,
+				Err(message) => return Err(message),
+			});
+		}
+		point.push(t);
+		helper::function_n(input_str, &names, &point)
+	};
+	let mut x = xi;
+	let mut dx = match f(&steps, ti, x) {
+		Ok(dx) => dx,
+		Err(message) => return Err(message),
+	};
+	for i in 0..nt {
+		let t = ti + (i as f64) * dt;
+		let t_new = if i + 1 == nt {tf} else {ti + ((i + 1) as f64) * dt};
+		let k1 = dx;
+		let k2 = match f(&steps, t + dt / 2., x + k1 * dt / 2.) {
+			Ok(k) => k,
+			Err(message) => return Err(message),
+		};
+		let k3 = match f(&steps, t + dt / 2., x + k2 * dt / 2.) {
+			Ok(k) => k,
+			Err(message) => return Err(message),
+		};
+		let k4 = match f(&steps, t + dt, x + k3 * dt) {
+			Ok(k) => k,
+			Err(message) => return Err(message),
+		};
+		let x_new = x + ((k1 + k4) + 2. * (k2 + k3)) * dt / 6.;
+		if !x_new.is_finite() {
+			return Err(format!("Error: the solution diverges at t = {}.", t_new));
+		}
+		// This is also the first stage of the next step.
+		let dx_new = match f(&steps, t_new, x_new) {
+			Ok(dx_new) => dx_new,
+			Err(message) => return Err(message),
+		};
+		steps.push(integrators::Step::hermite(t, t_new, &[x], &[k1], &[x_new], &[dx_new]));
+		x = x_new;
+		dx = dx_new;
+	}
+	let ts: Vec<f64> = (0..=nt).map(|i| if i == nt {tf} else {ti + (i as f64) * dt}).collect();
+	let xs = integrators::sample(&steps, &[xi], &ts).iter().map(|x| x[0]).collect();
+	Ok(Results {delays, ti, xi, tf, nt, ts, xs})
+}
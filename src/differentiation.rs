@@ -24,26 +24,23 @@ pub struct Results {
 	pub derivs: Vec<f64>,
 }
 
-pub fn raw (x_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
-	let x = match helper::parse_expression(x_str.to_string()) {
-	  Ok(x) => x,
-	  Err(message) => return Err(message),
-	};
-	let f = helper::function1(input_str.to_string(), x);
+// Values of f and of its first three derivatives at x, and whether or not f exists at x itself
+pub fn derivs<F: Fn(f64) -> Result<f64, String>>(f: &F, x: f64) -> Result<(bool, Vec<f64>), String> {
+	let f_x = f(x);
 	let dx = 0.001;
 	let steps = vec![2., 1., -1., -2.];
 	let mut fs = vec![];
 	for step in steps {
-	  fs.push(match helper::function1(input_str.to_string(), x + step * dx) {
+	  fs.push(match f(x + step * dx) {
 		Ok(f) => f,
 		Err(message) => return Err(message),
 	  });
 	}
 	let mut f0 = 0.;
 	// I prob need to implement better testing for this.
-	let nonsingular = f.is_ok();
+	let nonsingular = f_x.is_ok();
 	if nonsingular {
-	  f0 = f.unwrap();
+	  f0 = f_x.unwrap();
 	}
 	let derivs = vec![
 	  // How to use values at discrete points to calculate function and derivative values
@@ -53,6 +50,18 @@ pub fn raw (x_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
 	  if nonsingular {(fs[1] - 2. * f0 + fs[2]) / dx / dx} else {(fs[0] - fs[1] - fs[2] + fs[3]) / 3. / dx / dx},
 	  (fs[0] - fs[3] - 2. * fs[1] + 2. * fs[2]) / 2. / dx / dx / dx,
 	];
+	Ok((nonsingular, derivs))
+}
+
+pub fn raw (x_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
+	let x = match helper::parse_expression(x_str.to_string()) {
+	  Ok(x) => x,
+	  Err(message) => return Err(message),
+	};
+	let (nonsingular, derivs) = match derivs(&|x| helper::function1(input_str.to_string(), x), x) {
+	  Ok(result) => result,
+	  Err(message) => return Err(message),
+	};
 	Ok(Results {
 		x: x,
 		nonsingular: nonsingular,
@@ -0,0 +1,187 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::integrators;
+use crate::svg;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "DIRECTION FIELDS AND PHASE PORTRAITS".to_string(),
+		links:  calculus::links(22),
+		instructions: format!("This page draws a picture of the solutions of either a 1st-order differential equation <i>dx/dt</I> = function of <I>x</I> and <I>t</I> (as on the 1st-order ODE page) or a 2nd-order one <i>d</I><sup>2</sup><i>x/dt</i><sup>2</sup> = function of <I>x</I>, <I>v</I> (= <I>dx/dt</I>), and <I>t</I> (as on the 2nd-order ODE page), within a rectangular window.  For a 1st-order equation the horizontal and vertical coordinates are <i>t</i> and <i>x</i>, and the 'direction field' consists of short segments whose slopes are <i>dx/dt</i>.  For a 2nd-order equation the coordinates are <i>x</i> and <i>v</i>, and the 'phase portrait' consists of arrows in the direction of (<i>v</i>, <i>dv/dt</i>), ie in which the point (<i>x</i>, <i>v</i>) moves.  In the url bar after <tt>'https://basic-calculus.herokuapp.com/direction-field</tt> type the following:<p align=center>&sol;&lt;<tt>ode</tt> or <tt>ode2</tt>&gt;&sol;&lt;left edge&gt;&sol;&lt;right edge&gt;&sol;&lt;bottom edge&gt;&sol;&lt;top edge&gt;&sol;&lt;function&gt;</tt></p>The segments or arrows are drawn at the centers of a 20 &times; 20 grid of squares by default, a number which may be changed (to at most {}) by appending for instance <tt>?n=10</tt> to the url.  Trajectories (ie solutions of the equation), at most {} of them, may be overlaid by appending for instance <tt>initial=0,1,2</tt>.  For a 1st-order equation these are the values of <i>x</i> at the left edge of the window, and each trajectory extends to the right edge.  For a 2nd-order equation these are the values of <i>x</i> and <i>v</i> of each trajectory, in pairs, at the initial time (0 unless specified by appending for instance <tt>ti=1</tt>), and each trajectory extends to a final time which is 10 time-units later unless specified by appending for instance <tt>tf=20</tt>.  (A 2nd-order equation which depends upon <i>t</i> has a phase portrait which changes with time, so the arrows are drawn for the initial time.)  Each trajectory has 200 time-steps unless specified otherwise (up to {}) by appending for instance <tt>nt=400</tt>, and it ends if it wanders far outside the window.  The methods of the ODE pages may be chosen by appending for instance <tt>method=rk4</tt>, and the default method is <tt>rk45</tt>.  (If more than one option such as these is appended to the url, each after the first must be preceded by <tt>&</tt> rather than <tt>?</tt>.)", MAX_GRID, MAX_TRAJECTORIES, MAX_STEPS),
+		note: format!("{}{}", helper::NOTE1, helper::NOTE2).to_string(),
+		example: "For the logistic equation dx/dt = x(1 - x), type <tt>/ode/0/5/-0.5/1.5/x(1-x)?initial=0.1,0.5,1.2</tt> after /direction-field in the url above.  The direction field should show the solutions approaching x = 1, and the three trajectories should do so from below and from above.  For a damped pendulum, typing <tt>/ode2/-4/4/-3/3/-sin(x)-0.2v?initial=3,0,-3,2</tt> should show both trajectories spiraling in toward the stable equilibrium at x = v = 0.".to_string(),
+		algorithm: "The function is evaluated at the center of each square of the grid, and each segment or arrow has the same length, in order to show only the direction.  Each trajectory is calculated as on the 1st- or 2nd-order ODE page, with an event at each edge of a window which is three times as wide and tall as the one shown (and centered on it), which stops the integration.  The picture is a scalable vector graphic (SVG).".to_string(),
+		json: "Type '/json' in the url bar immediately after 'direction-field' if you would like the result in this format rather than html.  A successful response will contain nine properties. 'equation' is either 'ode' or 'ode2', 'left', 'right', 'bottom', and 'top' are the edges of the window, 'vectors' is the array of vectors of the direction field or phase portrait, each of which has the properties 'x' and 'y' (the horizontal and vertical coordinates of its center) and 'dx' and 'dy' (its horizontal and vertical components, ie 1 and <i>dx/dt</i> or <i>v</i> and <i>dv/dt</i>), 'initial' is the array of the initial values of the trajectories, 'trajectories' is the array of the corresponding arrays of points (each of which is the pair of horizontal and vertical coordinates), and 'svg' is the picture. An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+const MAX_GRID: usize = 100;
+const MAX_TRAJECTORIES: usize = 20;
+const MAX_STEPS: usize = 10000;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Vector {
+	pub x: f64,
+	pub y: f64,
+	pub dx: f64,
+	pub dy: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub equation: String,
+	pub left: f64,
+	pub right: f64,
+	pub bottom: f64,
+	pub top: f64,
+	pub vectors: Vec<Vector>,
+	pub initial: Vec<Vec<f64>>,
+	pub trajectories: Vec<Vec<[f64; 2]>>,
+	pub svg: String,
+}
+
+pub fn raw(
+	equation_str: &RawStr,
+	left_str: &RawStr,
+	right_str: &RawStr,
+	bottom_str: &RawStr,
+	top_str: &RawStr,
+	input_str: &RawStr,
+	initial_str: Option<&str>,
+	n_str: Option<&str>,
+	ti_str: Option<&str>,
+	tf_str: Option<&str>,
+	nt_str: Option<&str>,
+	method: Option<&str>,
+) -> Result<Results, String> {
+	let equation = equation_str.to_lowercase();
+	let second = match equation.as_str() {
+		"ode" => false,
+		"ode2" => true,
+		_ => return Err(format!("Error: the equation must be either ode or ode2, not '{}'.", equation)),
+	};
+	if !second && (ti_str.is_some() || tf_str.is_some()) {
+		return Err("Error: the times of the trajectories of a 1st-order equation are the left and right edges of the window.".to_string());
+	}
+	let settings = match integrators::settings(Some(method.unwrap_or("rk45")), None, None) {
+		Ok(settings) => settings,
+		Err(message) => return Err(message),
+	};
+	if settings.symplectic() && !second {
+		return Err(format!("Error: the {} method applies only to 2nd-order equations.", settings.method));
+	}
+	let mut edges = vec![];
+	for edge_str in [left_str, right_str, bottom_str, top_str] {
+		edges.push(match helper::parse_expression(edge_str.to_string()) {
+			Ok(edge) => edge,
+			Err(message) => return Err(message),
+		});
+	}
+	let (left, right, bottom, top) = (edges[0], edges[1], edges[2], edges[3]);
+	if right <= left || top <= bottom {
+		return Err("Error: the right and top edges of the window must exceed the left and bottom ones.".to_string());
+	}
+	let n = match helper::parse_count(n_str, 20, "size of the grid") {
+		Ok(n) => {
+			if n > MAX_GRID {
+				return Err(format!("Error: the grid may have at most {} squares on each side.", MAX_GRID));
+			}
+			n
+		},
+		Err(message) => return Err(message),
+	};
+	let nt = match helper::parse_count(nt_str, 200, "number of time-steps") {
+		Ok(nt) => {
+			if nt > MAX_STEPS {
+				return Err(format!("Error: each trajectory may have at most {} time-steps.", MAX_STEPS));
+			}
+			nt as i32
+		},
+		Err(message) => return Err(message),
+	};
+	let ti = match integrators::initial_time(ti_str) {
+		Ok(ti) => ti,
+		Err(message) => return Err(message),
+	};
+	let tf = match tf_str {
+		Some(tf_str) => match helper::parse_expression(tf_str.to_string()) {
+			Ok(tf) => tf,
+			Err(message) => return Err(message),
+		},
+		None => ti + 10.,
+	};
+	let mut values = vec![];
+	for value_str in initial_str.map_or(vec![], helper::parse_list) {
+		values.push(match helper::parse_expression(value_str) {
+			Ok(value) => value,
+			Err(message) => return Err(message),
+		});
+	}
+	if second && values.len() % 2 != 0 {
+		return Err("Error: the initial values of a 2nd-order equation must be pairs of values of x and v.".to_string());
+	}
+	let initial: Vec<Vec<f64>> = values.chunks(if second {2} else {1}).map(|chunk| chunk.to_vec()).collect();
+	if initial.len() > MAX_TRAJECTORIES {
+		return Err(format!("Error: there may be at most {} trajectories.", MAX_TRAJECTORIES));
+	}
+	let names: Vec<String> = (if second {vec!["x", "v", "t"]} else {vec!["x", "t"]}).iter().map(|name| name.to_string()).collect();
+	let f = |t: f64, y: &[f64]| -> Result<Vec<f64>, String> {
+		let mut point = y.to_vec();
+		point.push(t);
+		let derivative = match helper::function_n(input_str, &names, &point) {
+			Ok(derivative) => derivative,
+			Err(message) => return Err(message),
+		};
+		Ok(if second {vec![y[1], derivative]} else {vec![derivative]})
+	};
+	// The vector at each center of the grid is (1, dx/dt) or (v, dv/dt).
+	let (width, height) = ((right - left) / (n as f64), (top - bottom) / (n as f64));
+	let mut vectors = vec![];
+	for i in 0..n {
+		for j in 0..n {
+			let (x, y) = (left + (i as f64 + 0.5) * width, bottom + (j as f64 + 0.5) * height);
+			let (t, point) = if second {(ti, vec![x, y])} else {(x, vec![y])};
+			let derivative = match f(t, &point) {
+				Ok(derivative) => derivative,
+				Err(message) => return Err(message),
+			};
+			let (dx, dy) = if second {(derivative[0], derivative[1])} else {(1., derivative[0])};
+			vectors.push(Vector {x, y, dx, dy});
+		}
+	}
+	// A trajectory stops at the edge of the window which is three times as wide and tall.
+	let (dx, dy) = (right - left, top - bottom);
+	let mut edges = vec![format!("x-({})", bottom - dy), format!("x-({})", top + dy)];
+	if second {
+		edges = vec![format!("x-({})", left - dx), format!("x-({})", right + dx), format!("v-({})", bottom - dy), format!("v-({})", top + dy)];
+	}
+	let events = match integrators::events(Some(&edges.join(",")), Some("true"), &names) {
+		Ok(events) => events,
+		Err(message) => return Err(message),
+	};
+	let mut trajectories = vec![];
+	for yi in &initial {
+		let (t0, t1) = if second {(ti, tf)} else {(left, right)};
+		let solution = match integrators::solve(&f, t0, yi, t1, nt, &settings, &events) {
+			Ok(solution) => solution,
+			Err(message) => return Err(message),
+		};
+		trajectories.push(solution.ts.iter().zip(&solution.ys).map(|(t, y)| if second {[y[0], y[1]]} else {[*t, y[0]]}).collect::<Vec<[f64; 2]>>());
+	}
+	let frame = svg::Frame {left, right, bottom, top};
+	let (sx, sy) = frame.scales();
+	let length = 0.7 * (width * sx).min(height * sy);
+	let mut content = "".to_string();
+	for vector in &vectors {
+		content = format!("{}{}", content, svg::arrow(&frame, vector.x, vector.y, vector.dx, vector.dy, length, second));
+	}
+	for (k, trajectory) in trajectories.iter().enumerate() {
+		content = format!("{}{}", content, svg::polyline(&frame, trajectory, svg::color(k)));
+	}
+	let svg = if second {svg::image(&frame, &content, &[], "x", "v")} else {svg::image(&frame, &content, &[], "t", "x")};
+	Ok(Results {equation, left, right, bottom, top, vectors, initial, trajectories, svg})
+}
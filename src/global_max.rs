@@ -0,0 +1,99 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::max_finding;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "GLOBAL MAX- AND MIN-FINDING".to_string(),
+		links:  calculus::links(10),
+		instructions: "This page finds the global maximum and minimum of a function over a closed interval, along with all of the function's local maxima and minima in that interval.  In the url bar after <tt>'https://basic-calculus.herokuapp.com/global-max</tt> type the following:<p align=center>&sol;&lt;lower end of the interval&gt;&sol;&lt;upper end of the interval&gt;&sol;&lt;function of <i>x</I>&gt;</tt></p>An extremum which is narrower than the spacing between the samples (see below) may be missed.".to_string(),
+		note: format!("{}{}", helper::NOTE1, helper::NOTE2).to_string(),
+		example: "To find the global extrema of the function sin <i>x</i> + <i>x</i>/2 over the interval from <i>x</i> = 0 to 10, type <tt>/0/10/sin(x)+xd2</tt> after the current url address.  The global maximum should be at <tt>(8.377..., 5.054...)</tt>, and the global minimum should be at <tt>(0, 0)</tt>.".to_string(),
+		algorithm: "The function is sampled at 1000 evenly spaced intervals.  Each sample whose value exceeds (or is exceeded by) those of both of its neighbors brackets a local maximum (or minimum), which is then located by Brent's method.  The interval's endpoints are also local extrema.".to_string(),
+		json: "Type '/json' in the url bar immediately after 'global-max' if you would like the result in this format rather than html.  A successful response will contain five properties. 'a' and 'b' are the ends of the interval, 'max' and 'min' are the global maximum and minimum, and 'extrema' is an array of all local extrema, in order of increasing <i>x</i>.  Each extremum has four properties: 'x' and 'f' are its coordinates, 'kind' is either 'max' or 'min', and 'endpoint' is a boolean which indicates whether or not it is at an end of the interval. An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Extremum {
+	pub x: f64,
+	pub f: f64,
+	pub kind: String,
+	pub endpoint: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub a: f64,
+	pub b: f64,
+	pub max: Extremum,
+	pub min: Extremum,
+	pub extrema: Vec<Extremum>,
+}
+
+pub fn raw(a_str: &RawStr, b_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
+	let n = 1000;
+	let a = match helper::parse_expression(a_str.to_string()) {
+		Ok(a) => a,
+		Err(message) => return Err(message),
+	};
+	let b = match helper::parse_expression(b_str.to_string()) {
+		Ok(b) => b,
+		Err(message) => return Err(message),
+	};
+	if b <= a {
+		return Err(format!("Error: the upper end of the interval ({}) must exceed its lower end ({}).", b, a));
+	}
+	let f = |x: f64| helper::function1(input_str.to_string(), x);
+	let mut xs = vec![];
+	let mut fs = vec![];
+	for i in 0..=n {
+		let x = a + (b - a) * (i as f64) / (n as f64);
+		fs.push(match f(x) {
+			Ok(f) => f,
+			Err(message) => return Err(message),
+		});
+		xs.push(x);
+	}
+	// An endpoint takes its kind from the first sample which differs from it, so that a plateau there is labelled like one in the interior.  A constant function has a minimum at its lower end and a maximum at its upper one.
+	let endpoint_kind = |f: f64, other: Option<&f64>, constant: &str| match other {
+		Some(other) => (if *other < f {"max"} else {"min"}).to_string(),
+		None => constant.to_string(),
+	};
+	let mut extrema = vec![];
+	extrema.push(Extremum {x: a, f: fs[0], kind: endpoint_kind(fs[0], fs.iter().find(|f| **f != fs[0]), "min"), endpoint: true});
+	for i in 1..n {
+		// The asymmetric inequalities keep a plateau from yielding more than one extremum.
+		let sign = if fs[i] > fs[i - 1] && fs[i] >= fs[i + 1] {
+			1.
+		} else if fs[i] < fs[i - 1] && fs[i] <= fs[i + 1] {
+			-1.
+		} else {
+			continue;
+		};
+		// Brent's method finds a minimum, so a maximum of f is found as the minimum of -f.
+		let g = |x: f64| f(x).map(|f| -sign * f);
+		let (x, g, _) = match max_finding::brent(&g, xs[i - 1], xs[i + 1], xs[i], -sign * fs[i]) {
+			Ok(result) => result,
+			Err(message) => return Err(message),
+		};
+		extrema.push(Extremum {x, f: -sign * g, kind: (if sign > 0. {"max"} else {"min"}).to_string(), endpoint: false});
+	}
+	extrema.push(Extremum {x: b, f: fs[n], kind: endpoint_kind(fs[n], fs.iter().rev().find(|f| **f != fs[n]), "max"), endpoint: true});
+	// The first sample of the largest (smallest) value is always labelled a maximum (minimum), so neither search comes up empty.
+	let mut max: Option<Extremum> = None;
+	let mut min: Option<Extremum> = None;
+	for extremum in &extrema {
+		if extremum.kind == "max" && max.as_ref().map_or(true, |max| extremum.f > max.f) {
+			max = Some(extremum.clone());
+		}
+		if extremum.kind == "min" && min.as_ref().map_or(true, |min| extremum.f < min.f) {
+			min = Some(extremum.clone());
+		}
+	}
+	Ok(Results {a, b, max: max.unwrap(), min: min.unwrap(), extrema})
+}
@@ -71,6 +71,184 @@ pub fn function3(mut expression: String, x: f64, t: f64, v: f64) -> Result<f64,
 	parse_expression(expression.to_string())
 }
 
+// names of the unary functions in fn unary, needed to tell them apart from variable names
+const FUNCTIONS: [&str; 34] = ["abs", "acos", "acosh", "acot", "acoth", "acsc", "acsch", "asec", "asech", "asin", "asinh", "atan", "atanh", "cbrt", "ceil", "cos", "cot", "csc", "exp", "exp2", "exp_m1", "floor", "fract", "ln", "ln_1p", "log10", "log2", "round", "sec", "signum", "sin", "sqrt", "tan", "trunc"];
+
+// Splits a list (of variable names, values, or expressions) at its commas.
+pub fn parse_list(list_str: &str) -> Vec<String> {
+	let list = str::replace(&list_str.to_string(), "%2C", ",");
+	str::replace(&list, "%2c", ",").split(',').map(|item| item.to_string()).collect()
+}
+
+pub fn parse_names(names_str: &str) -> Result<Vec<String>, String> {
+	let names: Vec<String> = parse_list(&names_str.to_lowercase());
+	for (i, name) in names.iter().enumerate() {
+		if !name.starts_with(|c: char| c.is_ascii_alphabetic()) || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+			return Err(format!("Error: '{}' is not a legal variable name.  It must start with a letter and contain only letters, digits, and underscores.", name));
+		}
+		if FUNCTIONS.contains(&name.as_str()) || ["pi", "d", "div", "eq"].contains(&name.as_str()) {
+			return Err(format!("Error: '{}' is reserved and cannot be used as a variable name.", name));
+		}
+		if names[..i].contains(name) {
+			return Err(format!("Error: the variable name '{}' appears more than once.", name));
+		}
+	}
+	Ok(names)
+}
+
+// Parses an optional option which must be a positive integer, and which may be written as an expression.
+pub fn parse_count(count_str: Option<&str>, default: usize, name: &str) -> Result<usize, String> {
+	let count = match count_str {
+		Some(count_str) => match count_str.parse::<f64>() {
+			Ok(count) => count,
+			Err(_) => match parse_expression(count_str.to_string()) {
+				Ok(count) => count,
+				Err(message) => return Err(message),
+			},
+		},
+		None => return Ok(default),
+	};
+	if count.round() != count || count <= 0. {
+		return Err(format!("Error: the {} must be a positive integer, unlike {}.", name, count));
+	}
+	Ok(count as usize)
+}
+
+// Break a string of letters (and digits) into variable names, function names, and the like.
+// Longer names are tried first, so that a variable "xy" takes precedence over "x" times "y".
+fn split_word(word: &str, names: &[String], is_call: bool) -> Option<Vec<String>> {
+	if word.is_empty() {
+		return Some(vec![]);
+	}
+	// A function name can only appear at the end of a word, right before its argument.
+	if is_call && FUNCTIONS.contains(&word) {
+		return Some(vec![word.to_string()]);
+	}
+	let mut pieces: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+	pieces.append(&mut vec!["pi", "div", "d"]);
+	pieces.sort_by(|a, b| b.len().cmp(&a.len()));
+	for piece in pieces {
+		if word.starts_with(piece) {
+			if let Some(mut rest) = split_word(&word[piece.len()..], names, is_call) {
+				rest.insert(0, piece.to_string());
+				return Some(rest);
+			}
+		}
+	}
+	let n_digits = word.chars().take_while(|c| c.is_ascii_digit() || *c == '.').count();
+	if n_digits > 0 {
+		if let Some(mut rest) = split_word(&word[n_digits..], names, is_call) {
+			rest.insert(0, word[..n_digits].to_string());
+			return Some(rest);
+		}
+	}
+	None
+}
+
+// Replace each variable name in the expression by its value, leaving function names alone.
+fn substitute(expression: &str, names: &[String], values: &[f64]) -> String {
+	let chars: Vec<char> = expression.chars().collect();
+	let mut result = "".to_string();
+	let mut i = 0;
+	while i < chars.len() {
+		if !chars[i].is_ascii_alphabetic() {
+			result.push(chars[i]);
+			i += 1;
+			continue;
+		}
+		let start = i;
+		while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+			i += 1;
+		}
+		let word: String = chars[start..i].iter().collect();
+		let is_call = i < chars.len() && chars[i] == '(';
+		result += &match split_word(&word, names, is_call) {
+			// If the word cannot be split, the parser will report it.
+			None => word,
+			Some(pieces) => pieces.iter().map(|piece| match names.iter().position(|name| name == piece) {
+				Some(j) => format!("({})", values[j]),
+				None => piece.to_string(),
+			}).collect(),
+		};
+	}
+	result
+}
+
+// Spells each equals sign as such, whether it is url-encoded (%3D) or written as "eq" (in either case).
+// The latter counts only as a word of its own or a piece of one, so that variable names such as freq or eq1 are left alone.
+pub fn equals_signs(expression: &str, names: &[String]) -> String {
+	let expression = str::replace(&str::replace(expression, "%3d", "="), "%3D", "=");
+	let mut pieces = names.to_vec();
+	pieces.push("eq".to_string());
+	let chars: Vec<char> = expression.chars().collect();
+	let mut result = "".to_string();
+	let mut i = 0;
+	while i < chars.len() {
+		if !chars[i].is_ascii_alphabetic() {
+			result.push(chars[i]);
+			i += 1;
+			continue;
+		}
+		let start = i;
+		while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+			i += 1;
+		}
+		let word: String = chars[start..i].iter().collect();
+		let is_call = i < chars.len() && chars[i] == '(';
+		match split_word(&word.to_lowercase(), &pieces, is_call) {
+			// If the word cannot be split, the parser will report it.
+			None => result += &word,
+			Some(split) => {
+				// The other pieces keep their original case.
+				let mut j = 0;
+				for piece in split {
+					result += if piece == "eq" {"="} else {&word[j..j + piece.len()]};
+					j += piece.len();
+				}
+			},
+		}
+	}
+	result
+}
+
+// An equation (lhs = rhs) is rewritten as the function lhs - rhs, whose roots are the equation's solutions.
+// The equals sign may be url-encoded (%3D) or spelled as "eq".
+pub fn equation(expression: &str, names: &[String]) -> Result<String, String> {
+	let expression = equals_signs(&expression.to_lowercase(), names);
+	let mut sides = vec![];
+	let mut n_paren = 0;
+	let mut start = 0;
+	for (i, char) in expression.char_indices() {
+		n_paren += if char == '(' {1} else if char == ')' {-1} else {0};
+		if char == '=' {
+			if n_paren != 0 {
+				return Err(format!("Error: the equals sign in {} may not be inside parentheses.", expression));
+			}
+			sides.push(&expression[start..i]);
+			start = i + 1;
+		}
+	}
+	sides.push(&expression[start..]);
+	match sides.len() {
+		1 => Ok(expression.to_string()),
+		2 => {
+			if sides[0].is_empty() || sides[1].is_empty() {
+				return Err(format!("Error: one side of the equation {} is empty.", expression));
+			}
+			Ok(format!("({})-({})", sides[0], sides[1]))
+		},
+		_ => Err(format!("Error: {} contains more than one equals sign.", expression)),
+	}
+}
+
+// Evaluates a function of any number of named variables.
+pub fn function_n(expression: &str, names: &[String], values: &[f64]) -> Result<f64, String> {
+	let mut expression = expression.to_lowercase();
+	expression = str::replace(&expression, "%5e", "^");
+	expression = str::replace(&expression, "%20", "");
+	parse_expression(substitute(&expression, names, values))
+}
+
 fn find_size (expression: &str) -> Result<usize, String> {
 	let mut n_paren = 1; // leading (open)paren has been found, in calling function
 	for (n_expression, char) in expression.chars().enumerate() {
@@ -0,0 +1,1010 @@
+// Integrators for systems of first-order ODEs dy/dt = f(t, y), shared by the pages for differential equations
+
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::linear_algebra;
+use crate::root_finding;
+
+#[derive(Debug)]
+pub struct Settings {
+	pub method: String,
+	pub rtol: f64,
+	pub atol: f64,
+}
+
+impl Settings {
+	// Only the adaptive methods use the tolerances.
+	pub fn adaptive(&self) -> bool {
+		["rk45", "rosenbrock", "auto"].contains(&self.method.as_str())
+	}
+	// The symplectic methods apply only to equations for positions and velocities.
+	pub fn symplectic(&self) -> bool {
+		["verlet", "leapfrog", "yoshida4"].contains(&self.method.as_str())
+	}
+}
+
+// An accepted step from (t0, y0) to (t1, y1), within which the solution is interpolated by a polynomial
+#[derive(Debug, Clone)]
+pub struct Step {
+	pub t0: f64,
+	pub t1: f64,
+	pub y0: Vec<f64>,
+	pub y1: Vec<f64>,
+	bspl: Vec<f64>,
+	rcont4: Vec<f64>,
+	rcont5: Vec<f64>,
+}
+
+impl Step {
+	// cubic Hermite interpolant, determined by the values and derivatives at both ends
+	pub fn hermite(t0: f64, t1: f64, y0: &[f64], dy0: &[f64], y1: &[f64], dy1: &[f64]) -> Step {
+		let dt = t1 - t0;
+		let n = y0.len();
+		let bspl: Vec<f64> = (0..n).map(|j| dt * dy0[j] - (y1[j] - y0[j])).collect();
+		let rcont4 = (0..n).map(|j| (y1[j] - y0[j]) - dt * dy1[j] - bspl[j]).collect();
+		Step {t0, t1, y0: y0.to_vec(), y1: y1.to_vec(), bspl, rcont4, rcont5: vec![0.; n]}
+	}
+	pub fn interpolate(&self, t: f64) -> Vec<f64> {
+		if t == self.t1 {
+			return self.y1.clone();
+		}
+		let theta = (t - self.t0) / (self.t1 - self.t0);
+		let theta1 = 1. - theta;
+		(0..self.y0.len()).map(|j| {
+			self.y0[j] + theta * ((self.y1[j] - self.y0[j]) + theta1 * (self.bspl[j] + theta * (self.rcont4[j] + theta1 * self.rcont5[j])))
+		}).collect()
+	}
+}
+
+// expressions whose zeros are events, and whether or not the integration stops at the first one
+#[derive(Debug)]
+pub struct Events {
+	texts: Vec<String>,
+	expressions: Vec<String>,
+	// names of the variables, followed by that of the time
+	names: Vec<String>,
+	pub terminal: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Event {
+	pub event: String,
+	pub t: f64,
+	pub y: Vec<f64>,
+}
+
+#[derive(Debug)]
+pub struct Solution {
+	// the output times, which are evenly spaced unless the integration stops at an event
+	pub ts: Vec<f64>,
+	pub ys: Vec<Vec<f64>>,
+	pub steps: Vec<Step>,
+	pub accepted: i32,
+	pub rejected: i32,
+	// time at which the automatic method switches to the stiff solver
+	pub switched: Option<f64>,
+	pub events: Vec<Event>,
+}
+
+pub const INITIAL_TIME: &str = "The initial condition may instead be specified at a nonzero time by appending for instance <tt>?ti=1</tt> to the url.  The final time may also be less than the initial time, in which case the equation is integrated backward in time.  (If more than one option such as this is appended to the url, each after the first must be preceded by <tt>&</tt> rather than <tt>?</tt>.)";
+
+pub const EVENTS: &str = "Events, ie times at which functions of the variables and <i>t</i> vanish, may be found by appending for instance <tt>?events=x,v</tt> to the url.  An event may also be an equation such as <tt>x=1</tt> (in which case it is when the two sides are equal).  To stop the integration at the first event, also append <tt>&terminal=true</tt>.";
+
+pub const METHODS: &str = "Append <tt>?method=</tt> followed by one of the following to the url in order to choose a method other than the default (<tt>rk4</tt>) (see the algorithm below):<ul><li><tt>rk45</tt> for an adaptive step size</li><li><tt>euler</tt>, <tt>heun</tt> (the improved Euler method), <tt>midpoint</tt>, or <tt>rk3</tt> for simpler Runge-Kutta methods of orders 1, 2, 2, and 3, or <tt>abm4</tt> for the 4th-order Adams-Bashforth-Moulton predictor-corrector method, with time-steps of equal size (eg in order to compare their accuracies)</li><li><tt>rosenbrock</tt> for an adaptive step size, for stiff equations</li><li><tt>auto</tt> to switch from the former to the latter if the equation is found to be stiff</li><li><tt>backward-euler</tt> or <tt>bdf1</tt>, ..., <tt>bdf5</tt> for implicit methods with time-steps of equal size, for stiff equations</li></ul>For an adaptive method the number of time-steps is only the number of intervals at which the solution is reported.  The relative and absolute tolerances of each step of such a method (which by default are 10<sup>-6</sup> and 10<sup>-9</sup>) may be changed by also appending for instance <tt>&rtol=1e-8&atol=1e-12</tt>.";
+
+pub const SYMPLECTIC: &str = "For an equation whose acceleration does not depend upon <i>v</i> (eg for an orbit or an oscillator without friction), the 'symplectic' methods <tt>verlet</tt>, <tt>leapfrog</tt>, and <tt>yoshida4</tt> may also be chosen, with time-steps of equal size.  These do not let the energy drift steadily, as other methods do.  To see this, append for instance <tt>&energy=v**2/2+x**2/2</tt> (a function of <i>x</i>, <i>v</i>, and <i>t</i>) to the url, in which case the energy and its largest deviation from its initial value are also reported.";
+
+pub const ALGORITHM: &str = "By default the 4th-order Runge-Kutta method is used, with time-steps of equal size.  Each step of the <tt>euler</tt> method uses the derivative at its start, each step of the <tt>heun</tt> method uses the average of the derivatives at its start and at the end of an Euler step, each step of the <tt>midpoint</tt> method uses the derivative at the middle of a half-sized Euler step, and the <tt>rk3</tt> method is Kutta's 3rd-order method.  Each step of the <tt>abm4</tt> method predicts the solution by extrapolating the derivatives at the four previous times (Adams-Bashforth) and then corrects it by interpolating those at the three previous times and the predicted one (Adams-Moulton), after the first three steps are taken by the 4th-order Runge-Kutta method.  For the <tt>rk45</tt> method, the embedded Runge-Kutta method of Dormand and Prince is used: each step yields both 4th- and 5th-order solutions, the difference between which estimates the error of the step.  Steps whose error exceeds the tolerance are rejected and retried with a smaller step size, and the size of each step is chosen from the error of the previous one.  The <tt>rosenbrock</tt> method is the 2nd-order Rosenbrock method with a 3rd-order error estimate which is used by MATLAB's ode23s.  The <tt>auto</tt> method monitors the Dormand-Prince steps for stiffness by estimating (as Hairer and Wanner do) the product of the step size and the Jacobian's largest eigenvalue, and it switches to the Rosenbrock method if this product is near the edge of the Dormand-Prince method's region of stability for 15 steps in a row, ie if the steps are limited by stability rather than accuracy.  The <tt>bdf</tt> methods are the backward differentiation formulas of orders 1 (the backward Euler method) through 5, whose first steps (for which there are not yet enough previous values) are instead taken by the <tt>auto</tt> method with tight tolerances.  The <tt>verlet</tt> method is the velocity-Verlet method (half a step of changing <i>v</i>, a step of changing <i>x</i>, and half a step of changing <i>v</i>), the <tt>leapfrog</tt> method is the position-Verlet method (the same, with the roles of <i>x</i> and <i>v</i> exchanged), and the 4th-order <tt>yoshida4</tt> method consists of three velocity-Verlet steps, of which the middle one is backward.  The implicit equations are solved by Newton's method, and the Jacobian matrices needed by the Newton and Rosenbrock methods are calculated by finite differences.  The solution at the output times is calculated with each method's interpolant, as are the functions whose zeros are events.  Each change in the sign of such a function within one of four equal parts of a step brackets an event, which is then located as on the root-finding page.";
+
+// The initial time is 0 unless specified otherwise.
+pub fn initial_time(ti_str: Option<&str>) -> Result<f64, String> {
+	match ti_str {
+		Some(ti_str) => helper::parse_expression(ti_str.to_string()),
+		None => Ok(0.),
+	}
+}
+
+pub fn settings(method: Option<&str>, rtol_str: Option<&str>, atol_str: Option<&str>) -> Result<Settings, String> {
+	let methods = ["euler", "heun", "midpoint", "rk3", "rk4", "abm4", "rk45", "rosenbrock", "auto", "backward-euler", "bdf1", "bdf2", "bdf3", "bdf4", "bdf5", "verlet", "leapfrog", "yoshida4"];
+	let method = method.unwrap_or("rk4").to_lowercase();
+	if !methods.contains(&method.as_str()) {
+		return Err(format!("Error: the method must be one of {}, not '{}'.", methods.join(", "), method));
+	}
+	let mut tolerances = vec![];
+	for (tol_str, default) in [(rtol_str, 1e-6), (atol_str, 1e-9)] {
+		let tol = match tol_str {
+			Some(tol_str) => match parse_tolerance(tol_str) {
+				Ok(tol) => tol,
+				Err(message) => return Err(message),
+			},
+			None => default,
+		};
+		if tol <= 0. {
+			return Err(format!("Error: each tolerance must be positive, unlike {}.", tol));
+		}
+		tolerances.push(tol);
+	}
+	Ok(Settings {method, rtol: tolerances[0], atol: tolerances[1]})
+}
+
+// Tolerances may be written like 1e-8, which the expression parser would read as 1 times e - 8.
+fn parse_tolerance(tol_str: &str) -> Result<f64, String> {
+	match tol_str.parse::<f64>() {
+		Ok(tol) => Ok(tol),
+		Err(_) => helper::parse_expression(tol_str.to_string()),
+	}
+}
+
+pub fn events(events_str: Option<&str>, terminal_str: Option<&str>, names: &[String]) -> Result<Events, String> {
+	let terminal = match terminal_str.map(|terminal_str| terminal_str.to_lowercase()) {
+		None => false,
+		Some(terminal_str) => match terminal_str.as_str() {
+			"true" => true,
+			"false" => false,
+			_ => return Err(format!("Error: terminal must be either 'true' or 'false', not '{}'.", terminal_str)),
+		},
+	};
+	let mut texts = vec![];
+	let mut expressions = vec![];
+	if let Some(events_str) = events_str {
+		for event_str in helper::parse_list(events_str) {
+			let mut text = event_str.to_lowercase();
+			for (code, symbol) in [("%3d", "="), ("%5e", "^"), ("%20", "")] {
+				text = str::replace(&text, code, symbol);
+			}
+			// An equation is an event when its two sides are equal.
+			expressions.push(match helper::equation(&text, names) {
+				Ok(expression) => expression,
+				Err(message) => return Err(message),
+			});
+			texts.push(text);
+		}
+	}
+	if terminal && expressions.is_empty() {
+		return Err("Error: the integration cannot stop at the first event unless there are events.".to_string());
+	}
+	Ok(Events {texts, expressions, names: names.to_vec(), terminal})
+}
+
+impl Events {
+	pub fn none() -> Events {
+		Events {texts: vec![], expressions: vec![], names: vec![], terminal: false}
+	}
+	// value at (t, y) of the function whose zero is the kth event
+	fn value(&self, k: usize, t: f64, y: &[f64]) -> Result<f64, String> {
+		let mut point = y.to_vec();
+		point.push(t);
+		helper::function_n(&self.expressions[k], &self.names, &point)
+	}
+	// Find the events within a step, in order.
+	fn locate(&self, step: &Step) -> Result<Vec<Event>, String> {
+		let parts = 4;
+		let ts: Vec<f64> = (0..=parts).map(|i| if i == parts {step.t1} else {step.t0 + (i as f64) * (step.t1 - step.t0) / (parts as f64)}).collect();
+		let mut events = vec![];
+		for k in 0..self.expressions.len() {
+			let g = |t: f64| self.value(k, t, &step.interpolate(t));
+			let mut gs = vec![];
+			for t in &ts {
+				gs.push(match g(*t) {
+					Ok(g) => g,
+					Err(message) => return Err(message),
+				});
+			}
+			for i in 0..parts {
+				// A zero at the start of a part belongs to the previous part (or is the initial condition).
+				let t = if gs[i + 1] == 0. && gs[i] != 0. {
+					ts[i + 1]
+				} else if gs[i] * gs[i + 1] < 0. {
+					let (a, b, ga, gb) = if ts[i] < ts[i + 1] {(ts[i], ts[i + 1], gs[i], gs[i + 1])} else {(ts[i + 1], ts[i], gs[i + 1], gs[i])};
+					match root_finding::bracketed(&g, a, b, ga, gb, 1e-12, 100) {
+						Ok((t, _)) => t,
+						Err(message) => return Err(message),
+					}
+				} else {
+					continue;
+				};
+				events.push(Event {event: self.texts[k].clone(), t, y: step.interpolate(t)});
+			}
+		}
+		let direction = if step.t1 < step.t0 {-1.} else {1.};
+		events.sort_by(|a, b| (direction * a.t).partial_cmp(&(direction * b.t)).unwrap());
+		Ok(events)
+	}
+	// Record the events within a newly accepted step, and report whether or not the integration must stop.
+	fn check(&self, step: &Step, found: &mut Vec<Event>) -> Result<bool, String> {
+		if self.expressions.is_empty() {
+			return Ok(false);
+		}
+		let mut events = match self.locate(step) {
+			Ok(events) => events,
+			Err(message) => return Err(message),
+		};
+		if self.terminal && !events.is_empty() {
+			found.push(events[0].clone());
+			return Ok(true);
+		}
+		found.append(&mut events);
+		Ok(false)
+	}
+}
+
+// y + c * dy
+fn axpy(y: &[f64], c: f64, dy: &[f64]) -> Vec<f64> {
+	y.iter().zip(dy).map(|(y, dy)| y + c * dy).collect()
+}
+
+fn finite(y: &[f64], t: f64) -> Result<(), String> {
+	if y.iter().all(|y| y.is_finite()) {
+		Ok(())
+	} else {
+		Err(format!("Error: the solution diverges at t = {}.  Try a smaller time-step or an adaptive method.", t))
+	}
+}
+
+// Interpolate the solution at the given times, the first of which is the initial time.
+pub fn sample(steps: &[Step], yi: &[f64], ts: &[f64]) -> Vec<Vec<f64>> {
+	let direction = if ts[ts.len() - 1] < ts[0] {-1.} else {1.};
+	let mut ys = vec![yi.to_vec()];
+	let mut k = 0;
+	for t in &ts[1..] {
+		if steps.is_empty() {
+			ys.push(yi.to_vec());
+			continue;
+		}
+		while k + 1 < steps.len() && (t - steps[k].t1) * direction > 0. {
+			k += 1;
+		}
+		ys.push(steps[k].interpolate(*t));
+	}
+	ys
+}
+
+// forward-difference approximation of the Jacobian matrix of f with respect to y, where fy = f(t, y)
+pub fn jacobian<F: Fn(f64, &[f64]) -> Result<Vec<f64>, String>>(f: &F, t: f64, y: &[f64], fy: &[f64]) -> Result<Vec<Vec<f64>>, String> {
+	let n = y.len();
+	let mut jac = vec![vec![0.; n]; n];
+	for j in 0..n {
+		let dy = 1e-7 * y[j].abs().max(1.);
+		let mut y_new = y.to_vec();
+		y_new[j] += dy;
+		let fy_new = match f(t, &y_new) {
+			Ok(fy_new) => fy_new,
+			Err(message) => return Err(message),
+		};
+		for i in 0..n {
+			jac[i][j] = (fy_new[i] - fy[i]) / dy;
+		}
+	}
+	Ok(jac)
+}
+
+// I - c * jac
+fn iteration_matrix(jac: &[Vec<f64>], c: f64) -> Vec<Vec<f64>> {
+	(0..jac.len()).map(|i| (0..jac.len()).map(|j| (if i == j {1.} else {0.}) - c * jac[i][j]).collect()).collect()
+}
+
+pub fn solve<F: Fn(f64, &[f64]) -> Result<Vec<f64>, String>>(
+	f: &F,
+	ti: f64,
+	yi: &[f64],
+	tf: f64,
+	nt: i32,
+	settings: &Settings,
+	events: &Events,
+) -> Result<Solution, String> {
+	let solution = match settings.method.as_str() {
+		"euler" | "heun" | "midpoint" | "rk3" | "rk4" => runge_kutta(f, ti, yi, tf, nt, &settings.method, events),
+		"abm4" => abm4(f, ti, yi, tf, nt, events),
+		"backward-euler" => bdf(f, ti, yi, tf, nt, 1, events),
+		"bdf1" | "bdf2" | "bdf3" | "bdf4" | "bdf5" => bdf(f, ti, yi, tf, nt, settings.method[3..].parse().unwrap(), events),
+		"verlet" | "leapfrog" | "yoshida4" => symplectic(f, ti, yi, tf, nt, &settings.method, events),
+		_ => adaptive(f, ti, yi, tf, settings, events),
+	};
+	let mut solution = match solution {
+		Ok(solution) => solution,
+		Err(message) => return Err(message),
+	};
+	let direction = if tf < ti {-1.} else {1.};
+	let mut ts: Vec<f64> = (0..=nt).map(|i| if i == nt {tf} else {ti + (i as f64) * (tf - ti) / (nt as f64)}).collect();
+	// If the integration stops at an event, so does the output.
+	if events.terminal && !solution.events.is_empty() {
+		let t_stop = solution.events[0].t;
+		ts.retain(|t| (t - t_stop) * direction < 0.);
+		ts.push(t_stop);
+	}
+	solution.ys = sample(&solution.steps, yi, &ts);
+	solution.ts = ts;
+	Ok(solution)
+}
+
+// Estimate the largest Lyapunov exponent by following a second solution which starts close to the first one, and
+// moving it back to the same small distance in the same direction after each of the nt intervals (Benettin's method).
+pub fn lyapunov<F: Fn(f64, &[f64]) -> Result<Vec<f64>, String>>(
+	f: &F,
+	ti: f64,
+	yi: &[f64],
+	tf: f64,
+	nt: i32,
+	settings: &Settings,
+) -> Result<f64, String> {
+	// The exponent is a rate, which cannot be estimated from an interval of zero length.
+	if tf == ti {
+		return Err("Error: the Lyapunov exponent cannot be estimated unless the final time differs from the initial time.".to_string());
+	}
+	let n = yi.len();
+	let separation = 1e-8;
+	// The two solutions are integrated together (with the same steps), their positions before their velocities for a symplectic method.
+	let m = if n % 2 == 0 {n / 2} else {n};
+	let join = |y: &[f64], z: &[f64]| -> Vec<f64> {
+		let mut yz = y[..m].to_vec();
+		yz.extend_from_slice(&z[..m]);
+		yz.extend_from_slice(&y[m..]);
+		yz.extend_from_slice(&z[m..]);
+		yz
+	};
+	let split = |yz: &[f64]| -> (Vec<f64>, Vec<f64>) {
+		let (mut y, mut z) = (yz[..m].to_vec(), yz[m..2 * m].to_vec());
+		y.extend_from_slice(&yz[2 * m..2 * m + n - m]);
+		z.extend_from_slice(&yz[2 * m + n - m..]);
+		(y, z)
+	};
+	let g = |t: f64, yz: &[f64]| -> Result<Vec<f64>, String> {
+		let (y, z) = split(yz);
+		let dy = match f(t, &y) {
+			Ok(dy) => dy,
+			Err(message) => return Err(message),
+		};
+		let dz = match f(t, &z) {
+			Ok(dz) => dz,
+			Err(message) => return Err(message),
+		};
+		Ok(join(&dy, &dz))
+	};
+	let mut y = yi.to_vec();
+	let mut z: Vec<f64> = yi.iter().map(|y| y + separation / (n as f64).sqrt()).collect();
+	let mut sum = 0.;
+	for i in 0..nt {
+		let t = ti + (i as f64) * (tf - ti) / (nt as f64);
+		let t_new = if i + 1 == nt {tf} else {ti + ((i + 1) as f64) * (tf - ti) / (nt as f64)};
+		let solution = match solve(&g, t, &join(&y, &z), t_new, 1, settings, &Events::none()) {
+			Ok(solution) => solution,
+			Err(message) => return Err(message),
+		};
+		let (y_new, z_new) = split(&solution.ys[1]);
+		let distance = y_new.iter().zip(&z_new).map(|(y, z)| (z - y) * (z - y)).sum::<f64>().sqrt();
+		if distance == 0. {
+			return Err(format!("Error: the two solutions coincide at t = {}.", t_new));
+		}
+		sum += (distance / separation).ln();
+		z = y_new.iter().zip(&z_new).map(|(y, z)| y + (z - y) * separation / distance).collect();
+		y = y_new;
+	}
+	Ok(sum / (tf - ti).abs())
+}
+
+// Butcher tableau of an explicit Runge-Kutta method: nodes c, coefficients a (below the diagonal), and weights b
+struct Tableau {
+	c: Vec<f64>,
+	a: Vec<Vec<f64>>,
+	b: Vec<f64>,
+}
+
+fn tableau(method: &str) -> Tableau {
+	match method {
+		"euler" => Tableau {c: vec![0.], a: vec![vec![]], b: vec![1.]},
+		"heun" => Tableau {c: vec![0., 1.], a: vec![vec![], vec![1.]], b: vec![0.5, 0.5]},
+		"midpoint" => Tableau {c: vec![0., 0.5], a: vec![vec![], vec![0.5]], b: vec![0., 1.]},
+		"rk3" => Tableau {c: vec![0., 0.5, 1.], a: vec![vec![], vec![0.5], vec![-1., 2.]], b: vec![1. / 6., 2. / 3., 1. / 6.]},
+		_ => Tableau {
+			c: vec![0., 0.5, 0.5, 1.],
+			a: vec![vec![], vec![0.5], vec![0., 0.5], vec![0., 0., 1.]],
+			b: vec![1. / 6., 1. / 3., 1. / 3., 1. / 6.],
+		},
+	}
+}
+
+// The output times and solution are filled in by solve.
+pub fn runge_kutta<F: Fn(f64, &[f64]) -> Result<Vec<f64>, String>>(
+	f: &F,
+	ti: f64,
+	yi: &[f64],
+	tf: f64,
+	nt: i32,
+	method: &str,
+	events: &Events,
+) -> Result<Solution, String> {
+	let Tableau {c, a, b} = tableau(method);
+	let mut steps = vec![];
+	let mut found = vec![];
+	let mut y = yi.to_vec();
+	let mut dy = match f(ti, &y) {
+		Ok(dy) => dy,
+		Err(message) => return Err(message),
+	};
+	let dt = (tf - ti) / (nt as f64);
+	for i in 0..nt {
+		let t = ti + (i as f64) * (tf - ti) / (nt as f64);
+		let t_new = if i + 1 == nt {tf} else {ti + ((i + 1) as f64) * (tf - ti) / (nt as f64)};
+		let mut ks = vec![dy.clone()];
+		for j in 1..c.len() {
+			let mut y_stage = y.clone();
+			for (a_jl, k) in a[j].iter().zip(&ks) {
+				y_stage = axpy(&y_stage, a_jl * dt, k);
+			}
+			ks.push(match f(t + c[j] * dt, &y_stage) {
+				Ok(k) => k,
+				Err(message) => return Err(message),
+			});
+		}
+		let mut y_new = y.clone();
+		for (b_j, k) in b.iter().zip(&ks) {
+			y_new = axpy(&y_new, b_j * dt, k);
+		}
+		if let Err(message) = finite(&y_new, t + dt) {
+			return Err(message);
+		}
+		// This is also the first stage of the next step.
+		let dy_new = match f(t_new, &y_new) {
+			Ok(dy_new) => dy_new,
+			Err(message) => return Err(message),
+		};
+		steps.push(Step::hermite(t, t_new, &y, &dy, &y_new, &dy_new));
+		match events.check(&steps[i as usize], &mut found) {
+			Ok(true) => return Ok(Solution {ts: vec![], ys: vec![], steps, accepted: i + 1, rejected: 0, switched: None, events: found}),
+			Ok(false) => (),
+			Err(message) => return Err(message),
+		}
+		y = y_new;
+		dy = dy_new;
+	}
+	Ok(Solution {ts: vec![], ys: vec![], steps, accepted: nt, rejected: 0, switched: None, events: found})
+}
+
+// the 4th-order Adams-Bashforth-Moulton predictor-corrector method, whose first three steps are taken by the rk4 method
+pub fn abm4<F: Fn(f64, &[f64]) -> Result<Vec<f64>, String>>(f: &F, ti: f64, yi: &[f64], tf: f64, nt: i32, events: &Events) -> Result<Solution, String> {
+	let dt = (tf - ti) / (nt as f64);
+	let startup = 3.min(nt);
+	let t_startup = if startup == nt {tf} else {ti + (startup as f64) * (tf - ti) / (nt as f64)};
+	let solution = match runge_kutta(f, ti, yi, t_startup, startup, "rk4", events) {
+		Ok(solution) => solution,
+		Err(message) => return Err(message),
+	};
+	if startup == nt || (events.terminal && !solution.events.is_empty()) {
+		return Ok(solution);
+	}
+	let mut steps = solution.steps;
+	let mut found = solution.events;
+	// derivatives at the most recent four times, latest last
+	let mut dys = vec![];
+	for step in &steps {
+		dys.push(match f(step.t0, &step.y0) {
+			Ok(dy) => dy,
+			Err(message) => return Err(message),
+		});
+	}
+	let mut y = steps[steps.len() - 1].y1.clone();
+	dys.push(match f(t_startup, &y) {
+		Ok(dy) => dy,
+		Err(message) => return Err(message),
+	});
+	for i in startup..nt {
+		let t = ti + (i as f64) * (tf - ti) / (nt as f64);
+		let t_new = if i + 1 == nt {tf} else {ti + ((i + 1) as f64) * (tf - ti) / (nt as f64)};
+		let n = dys.len();
+		let combine = |coefficients: [f64; 4], dys: &[&Vec<f64>]| -> Vec<f64> {
+			let mut y_new = y.clone();
+			for (coefficient, dy) in coefficients.iter().zip(dys) {
+				y_new = axpy(&y_new, coefficient * dt / 24., dy);
+			}
+			y_new
+		};
+		// Predict with the Adams-Bashforth formula, and then correct with the Adams-Moulton formula.
+		let y_predicted = combine([55., -59., 37., -9.], &[&dys[n - 1], &dys[n - 2], &dys[n - 3], &dys[n - 4]]);
+		let dy_predicted = match f(t_new, &y_predicted) {
+			Ok(dy) => dy,
+			Err(message) => return Err(message),
+		};
+		let y_new = combine([9., 19., -5., 1.], &[&dy_predicted, &dys[n - 1], &dys[n - 2], &dys[n - 3]]);
+		if let Err(message) = finite(&y_new, t_new) {
+			return Err(message);
+		}
+		let dy_new = match f(t_new, &y_new) {
+			Ok(dy_new) => dy_new,
+			Err(message) => return Err(message),
+		};
+		steps.push(Step::hermite(t, t_new, &y, &dys[n - 1], &y_new, &dy_new));
+		match events.check(&steps[i as usize], &mut found) {
+			Ok(true) => return Ok(Solution {ts: vec![], ys: vec![], steps, accepted: i + 1, rejected: 0, switched: None, events: found}),
+			Ok(false) => (),
+			Err(message) => return Err(message),
+		}
+		y = y_new;
+		dys.remove(0);
+		dys.push(dy_new);
+	}
+	Ok(Solution {ts: vec![], ys: vec![], steps, accepted: nt, rejected: 0, switched: None, events: found})
+}
+
+// The first and second halves of y are the positions and velocities, whose derivatives are the velocities and accelerations.
+pub fn symplectic<F: Fn(f64, &[f64]) -> Result<Vec<f64>, String>>(
+	f: &F,
+	ti: f64,
+	yi: &[f64],
+	tf: f64,
+	nt: i32,
+	method: &str,
+	events: &Events,
+) -> Result<Solution, String> {
+	let n = yi.len();
+	if n % 2 != 0 {
+		return Err(format!("Error: the {} method only applies to equations for positions and velocities.", method));
+	}
+	let m = n / 2;
+	let acceleration = |t: f64, x: &[f64], v: &[f64]| -> Result<Vec<f64>, String> {
+		let mut y = x.to_vec();
+		y.extend_from_slice(v);
+		match f(t, &y) {
+			Ok(dy) => Ok(dy[m..].to_vec()),
+			Err(message) => Err(message),
+		}
+	};
+	// Yoshida's 4th-order method is a composition of three steps, the middle one of which is backward.
+	let cbrt2 = (2_f64).cbrt();
+	let weights = if method == "yoshida4" {vec![1. / (2. - cbrt2), -cbrt2 / (2. - cbrt2), 1. / (2. - cbrt2)]} else {vec![1.]};
+	let mut steps = vec![];
+	let mut found = vec![];
+	let mut y = yi.to_vec();
+	let mut dy = match f(ti, &y) {
+		Ok(dy) => dy,
+		Err(message) => return Err(message),
+	};
+	let dt = (tf - ti) / (nt as f64);
+	for i in 0..nt {
+		let t = ti + (i as f64) * (tf - ti) / (nt as f64);
+		let t_new = if i + 1 == nt {tf} else {ti + ((i + 1) as f64) * (tf - ti) / (nt as f64)};
+		let (mut x, mut v, mut s) = (y[..m].to_vec(), y[m..].to_vec(), t);
+		for weight in &weights {
+			let c = weight * dt;
+			if method == "leapfrog" {
+				x = axpy(&x, c / 2., &v);
+				let a = match acceleration(s + c / 2., &x, &v) {
+					Ok(a) => a,
+					Err(message) => return Err(message),
+				};
+				v = axpy(&v, c, &a);
+				x = axpy(&x, c / 2., &v);
+			} else {
+				let a0 = match acceleration(s, &x, &v) {
+					Ok(a) => a,
+					Err(message) => return Err(message),
+				};
+				v = axpy(&v, c / 2., &a0);
+				x = axpy(&x, c, &v);
+				let a1 = match acceleration(s + c, &x, &v) {
+					Ok(a) => a,
+					Err(message) => return Err(message),
+				};
+				v = axpy(&v, c / 2., &a1);
+			}
+			s += c;
+		}
+		let mut y_new = x;
+		y_new.extend(v);
+		if let Err(message) = finite(&y_new, t_new) {
+			return Err(message);
+		}
+		let dy_new = match f(t_new, &y_new) {
+			Ok(dy_new) => dy_new,
+			Err(message) => return Err(message),
+		};
+		steps.push(Step::hermite(t, t_new, &y, &dy, &y_new, &dy_new));
+		match events.check(&steps[i as usize], &mut found) {
+			Ok(true) => return Ok(Solution {ts: vec![], ys: vec![], steps, accepted: i + 1, rejected: 0, switched: None, events: found}),
+			Ok(false) => (),
+			Err(message) => return Err(message),
+		}
+		y = y_new;
+		dy = dy_new;
+	}
+	Ok(Solution {ts: vec![], ys: vec![], steps, accepted: nt, rejected: 0, switched: None, events: found})
+}
+
+// Solve the implicit equation y = rhs + c f(t, y) by Newton's method, starting from y0.
+fn implicit<F: Fn(f64, &[f64]) -> Result<Vec<f64>, String>>(f: &F, t: f64, y0: &[f64], rhs: &[f64], c: f64) -> Result<Vec<f64>, String> {
+	let iterations_max = 20;
+	let mut y = y0.to_vec();
+	for _ in 0..iterations_max {
+		let fy = match f(t, &y) {
+			Ok(fy) => fy,
+			Err(message) => return Err(message),
+		};
+		let jac = match jacobian(f, t, &y, &fy) {
+			Ok(jac) => jac,
+			Err(message) => return Err(message),
+		};
+		let g: Vec<f64> = (0..y.len()).map(|j| y[j] - rhs[j] - c * fy[j]).collect();
+		let delta = match linear_algebra::solve(&iteration_matrix(&jac, c), &g) {
+			Ok(delta) => delta,
+			Err(message) => return Err(message),
+		};
+		y = axpy(&y, -1., &delta);
+		if let Err(message) = finite(&y, t) {
+			return Err(message);
+		}
+		if linear_algebra::norm(&delta) <= 1e-10 * (1. + linear_algebra::norm(&y)) {
+			return Ok(y);
+		}
+	}
+	Err(format!("Error: Newton's method did not converge within {} iterations at t = {}.  Try more time-steps.", iterations_max, t))
+}
+
+// coefficients of the backward differentiation formulas y_(n+1) = sum_j a_j y_(n-j) + beta dt f(t_(n+1), y_(n+1))
+const BDF: [(&[f64], f64); 5] = [
+	(&[1.], 1.),
+	(&[4. / 3., -1. / 3.], 2. / 3.),
+	(&[18. / 11., -9. / 11., 2. / 11.], 6. / 11.),
+	(&[48. / 25., -36. / 25., 16. / 25., -3. / 25.], 12. / 25.),
+	(&[300. / 137., -300. / 137., 200. / 137., -75. / 137., 12. / 137.], 60. / 137.),
+];
+
+pub fn bdf<F: Fn(f64, &[f64]) -> Result<Vec<f64>, String>>(
+	f: &F,
+	ti: f64,
+	yi: &[f64],
+	tf: f64,
+	nt: i32,
+	order: usize,
+	events: &Events,
+) -> Result<Solution, String> {
+	let dt = (tf - ti) / (nt as f64);
+	let mut steps = vec![];
+	let mut found = vec![];
+	let mut ys = vec![yi.to_vec()];
+	let mut dy = match f(ti, yi) {
+		Ok(dy) => dy,
+		Err(message) => return Err(message),
+	};
+	for i in 0..(nt as usize) {
+		let t = ti + (i as f64) * (tf - ti) / (nt as f64);
+		let t_new = if i + 1 == nt as usize {tf} else {ti + ((i + 1) as f64) * (tf - ti) / (nt as f64)};
+		let y_new = if t_new == t {
+			// A step of zero length (when the final time is the initial one) changes nothing.
+			ys[i].clone()
+		} else if i + 1 < order {
+			// There are not yet enough previous values, so use an accurate adaptive method instead.
+			let startup = Settings {method: "auto".to_string(), rtol: 1e-10, atol: 1e-12};
+			match adaptive(f, t, &ys[i], t_new, &startup, &Events::none()) {
+				Ok(solution) => solution.steps[solution.steps.len() - 1].y1.clone(),
+				Err(message) => return Err(message),
+			}
+		} else {
+			let (a, beta) = BDF[order - 1];
+			let mut rhs = vec![0.; yi.len()];
+			for (j, a_j) in a.iter().enumerate() {
+				rhs = axpy(&rhs, *a_j, &ys[i - j]);
+			}
+			match implicit(f, t_new, &ys[i], &rhs, beta * dt) {
+				Ok(y_new) => y_new,
+				Err(message) => return Err(message),
+			}
+		};
+		let dy_new = match f(t_new, &y_new) {
+			Ok(dy_new) => dy_new,
+			Err(message) => return Err(message),
+		};
+		steps.push(Step::hermite(t, t_new, &ys[i], &dy, &y_new, &dy_new));
+		match events.check(&steps[i], &mut found) {
+			Ok(true) => return Ok(Solution {ts: vec![], ys: vec![], steps, accepted: i as i32 + 1, rejected: 0, switched: None, events: found}),
+			Ok(false) => (),
+			Err(message) => return Err(message),
+		}
+		ys.push(y_new);
+		dy = dy_new;
+	}
+	Ok(Solution {ts: vec![], ys: vec![], steps, accepted: nt, rejected: 0, switched: None, events: found})
+}
+
+// an attempted step of an adaptive method
+struct Attempt {
+	y: Vec<f64>,
+	dy: Vec<f64>,
+	// estimate of the error
+	err: Vec<f64>,
+	// coefficients of the interpolant (See Step.)
+	bspl: Vec<f64>,
+	rcont4: Vec<f64>,
+	rcont5: Vec<f64>,
+	// estimate of the product of the step size and the Jacobian's largest eigenvalue
+	stiffness: f64,
+}
+
+// Dormand-Prince coefficients (See Hairer, Norsett, and Wanner, "Solving Ordinary Differential Equations I".)
+const C: [f64; 7] = [0., 1. / 5., 3. / 10., 4. / 5., 8. / 9., 1., 1.];
+const A: [[f64; 6]; 7] = [
+	[0., 0., 0., 0., 0., 0.],
+	[1. / 5., 0., 0., 0., 0., 0.],
+	[3. / 40., 9. / 40., 0., 0., 0., 0.],
+	[44. / 45., -56. / 15., 32. / 9., 0., 0., 0.],
+	[19372. / 6561., -25360. / 2187., 64448. / 6561., -212. / 729., 0., 0.],
+	[9017. / 3168., -355. / 33., 46732. / 5247., 49. / 176., -5103. / 18656., 0.],
+	[35. / 384., 0., 500. / 1113., 125. / 192., -2187. / 6784., 11. / 84.],
+];
+// difference between the 5th- and 4th-order weights
+const E: [f64; 7] = [71. / 57600., 0., -71. / 16695., 71. / 1920., -17253. / 339200., 22. / 525., -1. / 40.];
+// weights of the 4th-order interpolant
+const D: [f64; 7] = [
+	-12715105075. / 11282082432., 0., 87487479700. / 32700410799., -10690763975. / 1880347072.,
+	701980252875. / 199316789632., -1453857185. / 822651844., 69997945. / 29380423.,
+];
+
+fn dopri5_step<F: Fn(f64, &[f64]) -> Result<Vec<f64>, String>>(f: &F, t: f64, y: &[f64], dy: &[f64], dt: f64) -> Result<Attempt, String> {
+	let n = y.len();
+	let mut ks = vec![dy.to_vec()];
+	let mut args = vec![y.to_vec()];
+	for s in 1..7 {
+		let mut y_s = y.to_vec();
+		for (r, k) in ks.iter().enumerate() {
+			y_s = axpy(&y_s, dt * A[s][r], k);
+		}
+		ks.push(match f(t + C[s] * dt, &y_s) {
+			Ok(k) => k,
+			Err(message) => return Err(message),
+		});
+		args.push(y_s);
+	}
+	// The solution is the argument of the last stage, whose derivative is the first stage of the next step.
+	let y_new = args[6].clone();
+	let dy_new = ks[6].clone();
+	let mut err = vec![0.; n];
+	let mut rcont5 = vec![0.; n];
+	for (s, k) in ks.iter().enumerate() {
+		err = axpy(&err, dt * E[s], k);
+		rcont5 = axpy(&rcont5, dt * D[s], k);
+	}
+	let bspl: Vec<f64> = (0..n).map(|j| dt * dy[j] - (y_new[j] - y[j])).collect();
+	let rcont4 = (0..n).map(|j| (y_new[j] - y[j]) - dt * dy_new[j] - bspl[j]).collect();
+	// The last two stages are both at the end of the step, which allows the Jacobian to be estimated.
+	let numerator = linear_algebra::norm(&axpy(&ks[6], -1., &ks[5]));
+	let denominator = linear_algebra::norm(&axpy(&args[6], -1., &args[5]));
+	let stiffness = if denominator > 0. {dt.abs() * numerator / denominator} else {0.};
+	Ok(Attempt {y: y_new, dy: dy_new, err, bspl, rcont4, rcont5, stiffness})
+}
+
+fn rosenbrock_step<F: Fn(f64, &[f64]) -> Result<Vec<f64>, String>>(f: &F, t: f64, y: &[f64], dy: &[f64], dt: f64) -> Result<Attempt, String> {
+	let n = y.len();
+	let d = 1. / (2. + 2_f64.sqrt());
+	let e32 = 6. + 2_f64.sqrt();
+	let jac = match jacobian(f, t, y, dy) {
+		Ok(jac) => jac,
+		Err(message) => return Err(message),
+	};
+	// partial derivative of f with respect to t
+	let delta = 1e-7 * t.abs().max(1.);
+	let dfdt: Vec<f64> = match f(t + delta, y) {
+		Ok(f_new) => (0..n).map(|j| (f_new[j] - dy[j]) / delta).collect(),
+		Err(message) => return Err(message),
+	};
+	let w = iteration_matrix(&jac, dt * d);
+	let k1 = match linear_algebra::solve(&w, &axpy(dy, dt * d, &dfdt)) {
+		Ok(k) => k,
+		Err(message) => return Err(message),
+	};
+	let f1 = match f(t + dt / 2., &axpy(y, dt / 2., &k1)) {
+		Ok(f1) => f1,
+		Err(message) => return Err(message),
+	};
+	let k2 = match linear_algebra::solve(&w, &axpy(&f1, -1., &k1)) {
+		Ok(k) => axpy(&k, 1., &k1),
+		Err(message) => return Err(message),
+	};
+	let y_new = axpy(y, dt, &k2);
+	let f2 = match f(t + dt, &y_new) {
+		Ok(f2) => f2,
+		Err(message) => return Err(message),
+	};
+	let b: Vec<f64> = (0..n).map(|j| f2[j] - e32 * (k2[j] - f1[j]) - 2. * (k1[j] - dy[j]) + dt * d * dfdt[j]).collect();
+	let k3 = match linear_algebra::solve(&w, &b) {
+		Ok(k) => k,
+		Err(message) => return Err(message),
+	};
+	Ok(Attempt {
+		y: y_new,
+		dy: f2,
+		err: (0..n).map(|j| dt / 6. * (k1[j] - 2. * k2[j] + k3[j])).collect(),
+		bspl: (0..n).map(|j| dt * (k1[j] - k2[j]) / (1. - 2. * d)).collect(),
+		rcont4: vec![0.; n],
+		rcont5: vec![0.; n],
+		stiffness: 0.,
+	})
+}
+
+fn adaptive<F: Fn(f64, &[f64]) -> Result<Vec<f64>, String>>(
+	f: &F,
+	ti: f64,
+	yi: &[f64],
+	tf: f64,
+	settings: &Settings,
+	events: &Events,
+) -> Result<Solution, String> {
+	let steps_max = 100_000;
+	let (rtol, atol) = (settings.rtol, settings.atol);
+	let n = yi.len();
+	let direction = if tf < ti {-1.} else {1.};
+	let scale = |y0: &[f64], y1: &[f64]| -> Vec<f64> {
+		(0..n).map(|j| atol + rtol * y0[j].abs().max(y1[j].abs())).collect()
+	};
+	// root-mean-square of a vector, weighted by the tolerances
+	let size = |v: &[f64], sc: &[f64]| -> f64 {
+		if n == 0 {return 0.;}
+		let scaled: Vec<f64> = v.iter().zip(sc).map(|(v, sc)| v / sc).collect();
+		linear_algebra::norm(&scaled) / (n as f64).sqrt()
+	};
+	let mut stiff = settings.method == "rosenbrock";
+	let mut switched = None;
+	// numbers of consecutive steps which do and do not seem to be stiff
+	let (mut stiff_steps, mut nonstiff_steps) = (0, 0);
+	let mut t = ti;
+	let mut y = yi.to_vec();
+	let mut dy = match f(t, &y) {
+		Ok(dy) => dy,
+		Err(message) => return Err(message),
+	};
+	let mut steps = vec![];
+	let mut found = vec![];
+	let mut accepted = 0;
+	let mut rejected = 0;
+	if tf == ti {
+		return Ok(Solution {ts: vec![], ys: vec![], steps, accepted, rejected, switched, events: vec![]});
+	}
+	// Choose the first step from the sizes of y and of its first two derivatives.
+	let sc = scale(&y, &y);
+	let (d0, d1) = (size(&y, &sc), size(&dy, &sc));
+	let h0 = if d0 < 1e-5 || d1 < 1e-5 {1e-6} else {0.01 * d0 / d1};
+	let dy0 = match f(t + direction * h0, &axpy(&y, direction * h0, &dy)) {
+		Ok(dy0) => dy0,
+		Err(message) => return Err(message),
+	};
+	let d2 = size(&axpy(&dy0, -1., &dy), &sc) / h0;
+	let h1 = if d1.max(d2) <= 1e-15 {(h0 * 1e-3).max(1e-6)} else {(0.01 / d1.max(d2)).powf(1. / 5.)};
+	let mut h = (100. * h0).min(h1).min((tf - ti).abs());
+	loop {
+		if accepted + rejected >= steps_max {
+			return Err(format!(
+				"Error: unable to reach t = {} within {} steps{}.",
+				tf,
+				steps_max,
+				if stiff {""} else {", which suggests that the equation is stiff (for which try method=auto or rosenbrock)"},
+			));
+		}
+		// Do not step past the final time.
+		let last = h >= (tf - t).abs();
+		if last {
+			h = (tf - t).abs();
+		}
+		if h <= 1e-14 * t.abs().max(1.) {
+			return Err(format!("Error: the step size has become too small at t = {}, which suggests that the solution is singular there.", t));
+		}
+		let dt = direction * h;
+		// the exponent for the step-size control, which depends upon the order of the method
+		let exponent = if stiff {1. / 3.} else {1. / 5.};
+		let attempt = if stiff {rosenbrock_step(f, t, &y, &dy, dt)} else {dopri5_step(f, t, &y, &dy, dt)};
+		let attempt = match attempt {
+			Ok(attempt) => attempt,
+			// Allow the step to be retried with a smaller size if f cannot be evaluated.
+			Err(message) => {
+				if h <= 1e-10 * t.abs().max(1.) {
+					return Err(message);
+				}
+				rejected += 1;
+				h *= 0.25;
+				continue;
+			},
+		};
+		let error = size(&attempt.err, &scale(&y, &attempt.y));
+		if !(error <= 1.) {
+			rejected += 1;
+			h *= if error.is_finite() {(0.9 * error.powf(-exponent)).max(0.2)} else {0.25};
+			continue;
+		}
+		accepted += 1;
+		let t_new = if last {tf} else {t + dt};
+		// Hairer and Wanner's test for stiffness, with a threshold which suits this step-size control
+		if settings.method == "auto" && !stiff {
+			if attempt.stiffness > 2.5 {
+				nonstiff_steps = 0;
+				stiff_steps += 1;
+				if stiff_steps == 15 {
+					stiff = true;
+					switched = Some(t_new);
+				}
+			} else {
+				nonstiff_steps += 1;
+				if nonstiff_steps == 6 {
+					stiff_steps = 0;
+				}
+			}
+		}
+		steps.push(Step {
+			t0: t,
+			t1: t_new,
+			y0: y,
+			y1: attempt.y.clone(),
+			bspl: attempt.bspl,
+			rcont4: attempt.rcont4,
+			rcont5: attempt.rcont5,
+		});
+		match events.check(&steps[steps.len() - 1], &mut found) {
+			Ok(true) => break,
+			Ok(false) => (),
+			Err(message) => return Err(message),
+		}
+		t = t_new;
+		y = attempt.y;
+		dy = attempt.dy;
+		h *= (0.9 * error.max(1e-10).powf(-exponent)).min(10.);
+		if last {
+			break;
+		}
+	}
+	Ok(Solution {ts: vec![], ys: vec![], steps, accepted, rejected, switched, events: found})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// dx/dt = x, whose solution x(t) = exp(t - 1) is integrated backward from t = 1 to t = 0
+	fn backward(method: &str, nt: i32, tolerance: f64) {
+		let f = |_t: f64, y: &[f64]| -> Result<Vec<f64>, String> {Ok(vec![y[0]])};
+		let settings = settings(Some(method), None, None).unwrap();
+		let solution = solve(&f, 1., &[1.], 0., nt, &settings, &Events::none()).unwrap();
+		assert_eq!(solution.ts.len(), nt as usize + 1);
+		assert_eq!(solution.ts[nt as usize], 0.);
+		for (t, y) in solution.ts.iter().zip(&solution.ys) {
+			assert!(solution.ts[0] >= *t);
+			assert!((y[0] - (t - 1.).exp()).abs() < tolerance, "{}: x({}) = {}", method, t, y[0]);
+		}
+	}
+
+	#[test]
+	fn backward_fixed_step() {
+		backward("euler", 100, 1e-2);
+		backward("rk4", 10, 1e-5);
+		backward("abm4", 20, 1e-5);
+		backward("bdf2", 100, 1e-4);
+	}
+
+	// The final time may equal the initial one, in which case the solution stays put.
+	#[test]
+	fn zero_length() {
+		let f = |_t: f64, y: &[f64]| -> Result<Vec<f64>, String> {Ok(vec![y[0]])};
+		for method in ["euler", "rk4", "abm4", "backward-euler", "bdf2", "bdf3", "bdf4", "bdf5", "rk45", "rosenbrock", "auto"] {
+			let solution = solve(&f, 1., &[1.], 1., 10, &settings(Some(method), None, None).unwrap(), &Events::none()).unwrap();
+			assert_eq!(solution.ts.len(), 11, "{}", method);
+			assert!(solution.ts.iter().zip(&solution.ys).all(|(t, y)| *t == 1. && y[0] == 1.), "{}", method);
+		}
+	}
+
+	#[test]
+	fn backward_adaptive() {
+		backward("rk45", 10, 1e-5);
+		backward("rosenbrock", 10, 1e-4);
+		backward("auto", 10, 1e-5);
+	}
+
+	#[test]
+	fn backward_event() {
+		let f = |_t: f64, y: &[f64]| -> Result<Vec<f64>, String> {Ok(vec![y[0]])};
+		let names = vec!["x".to_string(), "t".to_string()];
+		let events = events(Some("x=0.5"), Some("true"), &names).unwrap();
+		for method in ["rk4", "rk45"] {
+			let solution = solve(&f, 1., &[1.], 0., 10, &settings(Some(method), None, None).unwrap(), &events).unwrap();
+			let t = 1. - (2_f64).ln();
+			assert_eq!(solution.events.len(), 1);
+			assert!((solution.events[0].t - t).abs() < 1e-5, "{}: t = {}", method, solution.events[0].t);
+			assert_eq!(*solution.ts.last().unwrap(), solution.events[0].t);
+			assert!(solution.ts.iter().all(|t| *t >= solution.events[0].t));
+		}
+	}
+}
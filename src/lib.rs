@@ -2,15 +2,31 @@ pub const INSTRUCTIONS: &str = "WELCOME TO MY CALCULUS APP";
 
 const GITHUB: &str = "https://pknipp.github.io";
 const HEROKU: &str = "https://basic-calculus.herokuapp.com";
-const LINKS: [[&str; 4]; 8] = [
+const LINKS: [[&str; 4]; 24] = [
 	[GITHUB, "/math", "back to", " math APIs page"],
 	[HEROKU, "", "back to", " calculus page"],
 	[HEROKU, "/differentiation", "differentiation", ""],
 	[HEROKU, "/integration", "integration", ""],
 	[HEROKU, "/root-finding", "root-finding", ""],
 	[HEROKU, "/max-finding", "max-finding", ""],
+	[HEROKU, "/min-finding", "min-finding", ""],
 	[HEROKU, "/ode", "1st order", " differential equations"],
 	[HEROKU, "/ode2", "2nd order", " differential equations"],
+	[HEROKU, "/nonlinear-systems", "systems", " of nonlinear equations"],
+	[HEROKU, "/global-max", "global", " max- and min-finding"],
+	[HEROKU, "/optimization", "multivariable", " optimization"],
+	[HEROKU, "/constrained-optimization", "constrained", " optimization"],
+	[HEROKU, "/curve-fitting", "curve", " fitting"],
+	[HEROKU, "/analyze", "curve", " sketching"],
+	[HEROKU, "/odes", "systems", " of 1st-order differential equations"],
+	[HEROKU, "/bvp", "boundary-value", " problems"],
+	[HEROKU, "/sturm-liouville", "Sturm-Liouville", " eigenvalue problems"],
+	[HEROKU, "/oden", "nth order", " differential equations"],
+	[HEROKU, "/dde", "delay", " differential equations"],
+	[HEROKU, "/sde", "stochastic", " differential equations"],
+	[HEROKU, "/pde", "partial", " differential equations"],
+	[HEROKU, "/direction-field", "direction fields", " and phase portraits"],
+	[HEROKU, "/table", "tables", " of function values"],
 ];
 
 pub fn general_page() -> String {format!(
@@ -21,12 +37,11 @@ pub fn general_page() -> String {format!(
 
 pub fn links(n: i32) -> String {
 	let mut links = "".to_string();
-	for j in 0..8 {
-		let i = j as usize;
+	for (i, link) in LINKS.iter().enumerate() {
 		if i != n as usize {
 			links = format!("{}
 				<a href='{}{}'>{}</a>{}<br>",
-				links, LINKS[i][0], LINKS[i][1], LINKS[i][2], LINKS[i][3]
+				links, link[0], link[1], link[2], link[3]
 			);
 		}
 	}
@@ -0,0 +1,59 @@
+// Small dense linear-algebra routines shared by the multidimensional solvers.
+
+pub fn norm(x: &[f64]) -> f64 {
+	x.iter().map(|xi| xi * xi).sum::<f64>().sqrt()
+}
+
+pub fn dot(x: &[f64], y: &[f64]) -> f64 {
+	x.iter().zip(y).map(|(xi, yi)| xi * yi).sum()
+}
+
+// Solve the system a x = b using Gaussian elimination with partial pivoting.
+pub fn solve(a: &[Vec<f64>], b: &[f64]) -> Result<Vec<f64>, String> {
+	let n = b.len();
+	let mut a = a.to_vec();
+	let mut b = b.to_vec();
+	let scale = a.iter().flatten().fold(0., |max: f64, aij| max.max(aij.abs()));
+	for k in 0..n {
+		// Swap the row with the largest pivot into place.
+		let mut p = k;
+		for i in k + 1..n {
+			if a[i][k].abs() > a[p][k].abs() {
+				p = i;
+			}
+		}
+		if a[p][k].abs() <= scale * 1e-14 {
+			return Err("Error: the matrix of this linear system is singular.".to_string());
+		}
+		a.swap(k, p);
+		b.swap(k, p);
+		for i in k + 1..n {
+			let factor = a[i][k] / a[k][k];
+			for j in k..n {
+				a[i][j] -= factor * a[k][j];
+			}
+			b[i] -= factor * b[k];
+		}
+	}
+	// back substitution
+	let mut x = vec![0.; n];
+	for i in (0..n).rev() {
+		let sum: f64 = (i + 1..n).map(|j| a[i][j] * x[j]).sum();
+		x[i] = (b[i] - sum) / a[i][i];
+	}
+	Ok(x)
+}
+
+pub fn invert(a: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, String> {
+	let n = a.len();
+	let mut columns = vec![];
+	for j in 0..n {
+		let mut e = vec![0.; n];
+		e[j] = 1.;
+		columns.push(match solve(a, &e) {
+			Ok(column) => column,
+			Err(message) => return Err(message),
+		});
+	}
+	Ok((0..n).map(|i| (0..n).map(|j| columns[j][i]).collect()).collect())
+}
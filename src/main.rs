@@ -1,9 +1,11 @@
 #![feature(proc_macro_hygiene, decl_macro)]
 #[macro_use] extern crate rocket;
-use rocket::http::RawStr;
+use rocket::http::{ContentType, RawStr};
 use rocket::response::content;
 
 mod helper;
+mod linear_algebra;
+mod integrators;
 
 mod differentiation;
 mod integration;
@@ -11,6 +13,22 @@ mod root_finding;
 mod max_finding;
 mod ode;
 mod ode2;
+mod nonlinear_systems;
+mod global_max;
+mod optimization;
+mod constrained_optimization;
+mod curve_fitting;
+mod analysis;
+mod odes;
+mod bvp;
+mod sturm_liouville;
+mod oden;
+mod dde;
+mod sde;
+mod pde;
+mod svg;
+mod direction_field;
+mod table;
 
 extern crate calculus;
 extern crate serde_json;
@@ -40,6 +58,11 @@ fn max_finding_page() -> content::Html<String> {
   content::Html(max_finding::page())
 }
 
+#[get("/min-finding")]
+fn min_finding_page() -> content::Html<String> {
+  content::Html(max_finding::min_page())
+}
+
 #[get("/ode")]
 fn ode_page() -> content::Html<String> {
   content::Html(ode::page())
@@ -50,6 +73,81 @@ fn ode2_page() -> content::Html<String> {
   content::Html(ode2::page())
 }
 
+#[get("/nonlinear-systems")]
+fn nonlinear_systems_page() -> content::Html<String> {
+  content::Html(nonlinear_systems::page())
+}
+
+#[get("/global-max")]
+fn global_max_page() -> content::Html<String> {
+  content::Html(global_max::page())
+}
+
+#[get("/optimization")]
+fn optimization_page() -> content::Html<String> {
+  content::Html(optimization::page())
+}
+
+#[get("/constrained-optimization")]
+fn constrained_optimization_page() -> content::Html<String> {
+  content::Html(constrained_optimization::page())
+}
+
+#[get("/curve-fitting")]
+fn curve_fitting_page() -> content::Html<String> {
+  content::Html(curve_fitting::page())
+}
+
+#[get("/analyze")]
+fn analysis_page() -> content::Html<String> {
+  content::Html(analysis::page())
+}
+
+#[get("/odes")]
+fn odes_page() -> content::Html<String> {
+  content::Html(odes::page())
+}
+
+#[get("/bvp")]
+fn bvp_page() -> content::Html<String> {
+  content::Html(bvp::page())
+}
+
+#[get("/sturm-liouville")]
+fn sturm_liouville_page() -> content::Html<String> {
+  content::Html(sturm_liouville::page())
+}
+
+#[get("/oden")]
+fn oden_page() -> content::Html<String> {
+  content::Html(oden::page())
+}
+
+#[get("/dde")]
+fn dde_page() -> content::Html<String> {
+  content::Html(dde::page())
+}
+
+#[get("/sde")]
+fn sde_page() -> content::Html<String> {
+  content::Html(sde::page())
+}
+
+#[get("/pde")]
+fn pde_page() -> content::Html<String> {
+  content::Html(pde::page())
+}
+
+#[get("/direction-field")]
+fn direction_field_page() -> content::Html<String> {
+  content::Html(direction_field::page())
+}
+
+#[get("/table")]
+fn table_page() -> content::Html<String> {
+  content::Html(table::page())
+}
+
 #[get("/differentiation/json/<x_str>/<input_str>")]
 fn differentiation_json(x_str: &RawStr, input_str: &RawStr) -> String {
   match differentiation::raw(x_str, input_str) {
@@ -74,41 +172,421 @@ fn root_finding_json(x_str: &RawStr, input_str: &RawStr) -> String {
   }
 }
 
-#[get("/max-finding/json/<x_str>/<input_str>")]
-fn max_finding_json(x_str: &RawStr, input_str: &RawStr) -> String {
-  match max_finding::raw(x_str, input_str) {
+#[get("/max-finding/json/<x_str>/<input_str>?<mode>")]
+fn max_finding_json(x_str: &RawStr, input_str: &RawStr, mode: Option<&RawStr>) -> String {
+  match max_finding::raw(x_str, input_str, mode.map_or("max", |mode| mode.as_str())) {
     Ok(results) => serde_json::to_string(&results).unwrap(),
     Err(message) => format!("{{\"message\": {}}}", message),
   }
 }
 
-#[get("/ode/json/<x_str>/<t_str>/<nt_str>/<input_str>")]
+#[get("/min-finding/json/<x_str>/<input_str>?<mode>")]
+fn min_finding_json(x_str: &RawStr, input_str: &RawStr, mode: Option<&RawStr>) -> String {
+  match max_finding::raw(x_str, input_str, mode.map_or("min", |mode| mode.as_str())) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/ode/json/<x_str>/<t_str>/<nt_str>/<input_str>?<ti>&<method>&<rtol>&<atol>&<events>&<terminal>&<exact>")]
 fn ode_json(
   x_str: &RawStr,
   t_str: &RawStr,
   nt_str: &RawStr,
-  input_str: &RawStr
+  input_str: &RawStr,
+  ti: Option<&RawStr>,
+  method: Option<&RawStr>,
+  rtol: Option<&RawStr>,
+  atol: Option<&RawStr>,
+  events: Option<&RawStr>,
+  terminal: Option<&RawStr>,
+  exact: Option<&RawStr>,
 ) -> String {
-  match ode::raw(x_str, t_str, nt_str, input_str) {
+  match ode::raw(
+    x_str,
+    t_str,
+    nt_str,
+    input_str,
+    ti.map(|ti| ti.as_str()),
+    method.map(|method| method.as_str()),
+    rtol.map(|rtol| rtol.as_str()),
+    atol.map(|atol| atol.as_str()),
+    events.map(|events| events.as_str()),
+    terminal.map(|terminal| terminal.as_str()),
+    exact.map(|exact| exact.as_str()),
+  ) {
     Ok(results) => serde_json::to_string(&results).unwrap(),
     Err(message) => format!("{{\"message\": {}}}", message),
   }
 }
 
-#[get("/ode2/json/<x_str>/<v_str>/<t_str>/<nt_str>/<input_str>")]
+#[get("/ode2/json/<x_str>/<v_str>/<t_str>/<nt_str>/<input_str>?<ti>&<method>&<rtol>&<atol>&<events>&<terminal>&<energy>&<period>&<lyapunov>")]
 fn ode2_json(
   x_str: &RawStr,
   v_str: &RawStr,
   t_str: &RawStr,
   nt_str: &RawStr,
-  input_str: &RawStr
+  input_str: &RawStr,
+  ti: Option<&RawStr>,
+  method: Option<&RawStr>,
+  rtol: Option<&RawStr>,
+  atol: Option<&RawStr>,
+  events: Option<&RawStr>,
+  terminal: Option<&RawStr>,
+  energy: Option<&RawStr>,
+  period: Option<&RawStr>,
+  lyapunov: Option<&RawStr>,
+) -> String {
+  match ode2::raw(
+    x_str,
+    v_str,
+    t_str,
+    nt_str,
+    input_str,
+    ti.map(|ti| ti.as_str()),
+    method.map(|method| method.as_str()),
+    rtol.map(|rtol| rtol.as_str()),
+    atol.map(|atol| atol.as_str()),
+    events.map(|events| events.as_str()),
+    terminal.map(|terminal| terminal.as_str()),
+    energy.map(|energy| energy.as_str()),
+    period.map(|period| period.as_str()),
+    lyapunov.map(|lyapunov| lyapunov.as_str()),
+  ) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/nonlinear-systems/json/<vars_str>/<xi_str>/<input_str>")]
+fn nonlinear_systems_json(vars_str: &RawStr, xi_str: &RawStr, input_str: &RawStr) -> String {
+  match nonlinear_systems::raw(vars_str, xi_str, input_str) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/global-max/json/<a_str>/<b_str>/<input_str>")]
+fn global_max_json(a_str: &RawStr, b_str: &RawStr, input_str: &RawStr) -> String {
+  match global_max::raw(a_str, b_str, input_str) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/optimization/json/<vars_str>/<xi_str>/<input_str>?<method>&<mode>")]
+fn optimization_json(
+  vars_str: &RawStr,
+  xi_str: &RawStr,
+  input_str: &RawStr,
+  method: Option<&RawStr>,
+  mode: Option<&RawStr>,
+) -> String {
+  match optimization::raw(
+    vars_str,
+    xi_str,
+    input_str,
+    method.map_or("bfgs", |method| method.as_str()),
+    mode.map_or("min", |mode| mode.as_str()),
+  ) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/constrained-optimization/json/<vars_str>/<xi_str>/<constraints_str>/<input_str>?<mode>&<lower>&<upper>")]
+fn constrained_optimization_json(
+  vars_str: &RawStr,
+  xi_str: &RawStr,
+  constraints_str: &RawStr,
+  input_str: &RawStr,
+  mode: Option<&RawStr>,
+  lower: Option<&RawStr>,
+  upper: Option<&RawStr>,
+) -> String {
+  match constrained_optimization::raw(
+    vars_str,
+    xi_str,
+    constraints_str,
+    input_str,
+    mode.map_or("min", |mode| mode.as_str()),
+    lower.map(|lower| lower.as_str()),
+    upper.map(|upper| upper.as_str()),
+  ) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[post("/curve-fitting", data = "<body>")]
+fn curve_fitting_json(body: String) -> String {
+  match curve_fitting::raw(&body) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/analyze/json/<a_str>/<b_str>/<input_str>")]
+fn analysis_json(a_str: &RawStr, b_str: &RawStr, input_str: &RawStr) -> String {
+  match analysis::raw(a_str, b_str, input_str) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/odes/json/<vars_str>/<xi_str>/<tf_str>/<nt_str>/<input_str>?<ti>&<method>&<rtol>&<atol>&<events>&<terminal>")]
+fn odes_json(
+  vars_str: &RawStr,
+  xi_str: &RawStr,
+  tf_str: &RawStr,
+  nt_str: &RawStr,
+  input_str: &RawStr,
+  ti: Option<&RawStr>,
+  method: Option<&RawStr>,
+  rtol: Option<&RawStr>,
+  atol: Option<&RawStr>,
+  events: Option<&RawStr>,
+  terminal: Option<&RawStr>,
+) -> String {
+  match odes::raw(
+    vars_str,
+    xi_str,
+    tf_str,
+    nt_str,
+    input_str,
+    ti.map(|ti| ti.as_str()),
+    method.map(|method| method.as_str()),
+    rtol.map(|rtol| rtol.as_str()),
+    atol.map(|atol| atol.as_str()),
+    events.map(|events| events.as_str()),
+    terminal.map(|terminal| terminal.as_str()),
+  ) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/bvp/json/<xi_str>/<xf_str>/<tf_str>/<nt_str>/<input_str>?<ti>&<method>")]
+fn bvp_json(
+  xi_str: &RawStr,
+  xf_str: &RawStr,
+  tf_str: &RawStr,
+  nt_str: &RawStr,
+  input_str: &RawStr,
+  ti: Option<&RawStr>,
+  method: Option<&RawStr>,
+) -> String {
+  match bvp::raw(xi_str, xf_str, tf_str, nt_str, input_str, ti.map(|ti| ti.as_str()), method.map(|method| method.as_str())) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/sturm-liouville/json/<a_str>/<b_str>/<n_str>/<q_str>?<w>&<left>&<right>&<nx>")]
+fn sturm_liouville_json(
+  a_str: &RawStr,
+  b_str: &RawStr,
+  n_str: &RawStr,
+  q_str: &RawStr,
+  w: Option<&RawStr>,
+  left: Option<&RawStr>,
+  right: Option<&RawStr>,
+  nx: Option<&RawStr>,
+) -> String {
+  match sturm_liouville::raw(
+    a_str,
+    b_str,
+    n_str,
+    q_str,
+    w.map(|w| w.as_str()),
+    left.map(|left| left.as_str()),
+    right.map(|right| right.as_str()),
+    nx.map(|nx| nx.as_str()),
+  ) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/oden/json/<order_str>/<xi_str>/<tf_str>/<nt_str>/<input_str>?<ti>&<method>&<rtol>&<atol>&<events>&<terminal>")]
+fn oden_json(
+  order_str: &RawStr,
+  xi_str: &RawStr,
+  tf_str: &RawStr,
+  nt_str: &RawStr,
+  input_str: &RawStr,
+  ti: Option<&RawStr>,
+  method: Option<&RawStr>,
+  rtol: Option<&RawStr>,
+  atol: Option<&RawStr>,
+  events: Option<&RawStr>,
+  terminal: Option<&RawStr>,
+) -> String {
+  match oden::raw(
+    order_str,
+    xi_str,
+    tf_str,
+    nt_str,
+    input_str,
+    ti.map(|ti| ti.as_str()),
+    method.map(|method| method.as_str()),
+    rtol.map(|rtol| rtol.as_str()),
+    atol.map(|atol| atol.as_str()),
+    events.map(|events| events.as_str()),
+    terminal.map(|terminal| terminal.as_str()),
+  ) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/dde/json/<delays_str>/<history_str>/<tf_str>/<nt_str>/<input_str>?<ti>")]
+fn dde_json(
+  delays_str: &RawStr,
+  history_str: &RawStr,
+  tf_str: &RawStr,
+  nt_str: &RawStr,
+  input_str: &RawStr,
+  ti: Option<&RawStr>,
+) -> String {
+  match dde::raw(delays_str, history_str, tf_str, nt_str, input_str, ti.map(|ti| ti.as_str())) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/sde/json/<xi_str>/<tf_str>/<nt_str>/<drift_str>/<diffusion_str>?<ti>&<method>&<seed>&<paths>")]
+fn sde_json(
+  xi_str: &RawStr,
+  tf_str: &RawStr,
+  nt_str: &RawStr,
+  drift_str: &RawStr,
+  diffusion_str: &RawStr,
+  ti: Option<&RawStr>,
+  method: Option<&RawStr>,
+  seed: Option<&RawStr>,
+  paths: Option<&RawStr>,
+) -> String {
+  match sde::raw(
+    xi_str,
+    tf_str,
+    nt_str,
+    drift_str,
+    diffusion_str,
+    ti.map(|ti| ti.as_str()),
+    method.map(|method| method.as_str()),
+    seed.map(|seed| seed.as_str()),
+    paths.map(|paths| paths.as_str()),
+  ) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/pde/json/<equation_str>/<coefficient_str>/<a_str>/<b_str>/<nx_str>/<initial_str>/<tf_str>/<nt_str>?<source>&<velocity>&<left>&<right>&<ti>&<method>&<rtol>&<atol>")]
+fn pde_json(
+  equation_str: &RawStr,
+  coefficient_str: &RawStr,
+  a_str: &RawStr,
+  b_str: &RawStr,
+  nx_str: &RawStr,
+  initial_str: &RawStr,
+  tf_str: &RawStr,
+  nt_str: &RawStr,
+  source: Option<&RawStr>,
+  velocity: Option<&RawStr>,
+  left: Option<&RawStr>,
+  right: Option<&RawStr>,
+  ti: Option<&RawStr>,
+  method: Option<&RawStr>,
+  rtol: Option<&RawStr>,
+  atol: Option<&RawStr>,
+) -> String {
+  match pde::raw(
+    equation_str,
+    coefficient_str,
+    a_str,
+    b_str,
+    nx_str,
+    initial_str,
+    tf_str,
+    nt_str,
+    source.map(|source| source.as_str()),
+    velocity.map(|velocity| velocity.as_str()),
+    left.map(|left| left.as_str()),
+    right.map(|right| right.as_str()),
+    ti.map(|ti| ti.as_str()),
+    method.map(|method| method.as_str()),
+    rtol.map(|rtol| rtol.as_str()),
+    atol.map(|atol| atol.as_str()),
+  ) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/direction-field/json/<equation_str>/<left_str>/<right_str>/<bottom_str>/<top_str>/<input_str>?<initial>&<n>&<ti>&<tf>&<nt>&<method>")]
+fn direction_field_json(
+  equation_str: &RawStr,
+  left_str: &RawStr,
+  right_str: &RawStr,
+  bottom_str: &RawStr,
+  top_str: &RawStr,
+  input_str: &RawStr,
+  initial: Option<&RawStr>,
+  n: Option<&RawStr>,
+  ti: Option<&RawStr>,
+  tf: Option<&RawStr>,
+  nt: Option<&RawStr>,
+  method: Option<&RawStr>,
+) -> String {
+  match direction_field::raw(
+    equation_str,
+    left_str,
+    right_str,
+    bottom_str,
+    top_str,
+    input_str,
+    initial.map(|initial| initial.as_str()),
+    n.map(|n| n.as_str()),
+    ti.map(|ti| ti.as_str()),
+    tf.map(|tf| tf.as_str()),
+    nt.map(|nt| nt.as_str()),
+    method.map(|method| method.as_str()),
+  ) {
+    Ok(results) => serde_json::to_string(&results).unwrap(),
+    Err(message) => format!("{{\"message\": {}}}", message),
+  }
+}
+
+#[get("/table/json/<a_str>/<b_str>/<n_str>/<input_str>?<spacing>")]
+fn table_json(
+  a_str: &RawStr,
+  b_str: &RawStr,
+  n_str: &RawStr,
+  input_str: &RawStr,
+  spacing: Option<&RawStr>,
 ) -> String {
-  match ode2::raw(x_str, v_str, t_str, nt_str, input_str) {
+  match table::raw(a_str, b_str, n_str, input_str, spacing.map(|spacing| spacing.as_str())) {
     Ok(results) => serde_json::to_string(&results).unwrap(),
     Err(message) => format!("{{\"message\": {}}}", message),
   }
 }
 
+#[get("/table/csv/<a_str>/<b_str>/<n_str>/<input_str>?<spacing>")]
+fn table_csv(
+  a_str: &RawStr,
+  b_str: &RawStr,
+  n_str: &RawStr,
+  input_str: &RawStr,
+  spacing: Option<&RawStr>,
+) -> content::Content<String> {
+  match table::raw(a_str, b_str, n_str, input_str, spacing.map(|spacing| spacing.as_str())) {
+    Ok(results) => content::Content(ContentType::CSV, table::csv(&results)),
+    Err(message) => content::Content(ContentType::Plain, message),
+  }
+}
+
 #[get("/differentiation/<x_str>/<input_str>")]
 fn differentiation_html(x_str: &RawStr, input_str: &RawStr) -> content::Html<String> {
   let instructions = differentiation::page();
@@ -130,10 +608,18 @@ fn differentiation_html(x_str: &RawStr, input_str: &RawStr) -> content::Html<Str
   for stri in ["div", "DIV", "d", "D"] {
     expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
   }
+  // the function and its tangent line near the point
+  let (x, f, slope) = (results.x, results.derivs[0], results.derivs[1]);
+  let half = x.abs().max(2.) / 2.;
+  let plot = svg::plot(&[
+    svg::Series {label: "f(x)".to_string(), points: svg::graph(input_str, x - half, x + half), style: svg::Style::Line},
+    svg::Series {label: "tangent".to_string(), points: vec![[x - half / 2., f - slope * half / 2.], [x + half / 2., f + slope * half / 2.]], style: svg::Style::Line},
+    svg::Series {label: format!("x = {}", x), points: vec![[x, f]], style: svg::Style::Dots},
+  ], "x", "f");
   content::Html(format!(
     "{}<br><br><b>results</b> at x = {} for the function f(x) =
     {}:{}<ul><li>f = {}</li><li>f' = {}</li><li>f'' =
-    {}</li><li>f''' = {}</li></ul>",
+    {}</li><li>f''' = {}</li></ul>{}",
     instructions,
     results.x,
     expression,
@@ -142,6 +628,7 @@ fn differentiation_html(x_str: &RawStr, input_str: &RawStr) -> content::Html<Str
     results.derivs[1],
     results.derivs[2],
     results.derivs[3],
+    plot,
   ))
 }
 
@@ -170,10 +657,17 @@ fn integration_html(
   for stri in ["div", "DIV", "d", "D"] {
     expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
   }
+  // the function, with the area under it shaded
+  let (a, b) = (results.xi.min(results.xf), results.xi.max(results.xf));
+  let margin = (b - a) / 10.;
+  let plot = svg::plot(&[
+    svg::Series {label: "f(x)".to_string(), points: svg::graph(input_str, a - margin, b + margin), style: svg::Style::Line},
+    svg::Series {label: "integral".to_string(), points: svg::graph(input_str, a, b), style: svg::Style::Area},
+  ], "x", "f");
   content::Html(format!(
     "{}<br><br><b>result</b>: {} equals the definite integral from x = {}
     to x = {} of the function f(x) = {}.<br>Convergence to an absolute
-    accuracy of {} required {} subdivisions.",
+    accuracy of {} required {} subdivisions.{}",
     instructions,
     results.integral,
     results.xi,
@@ -181,6 +675,7 @@ fn integration_html(
     str::replace(&expression, "X", "x"),
     results.epsilon,
     results.subdivisions,
+    plot,
   ))
 }
 
@@ -201,33 +696,51 @@ fn root_finding_html(xi_str: &RawStr, input_str: &RawStr) -> content::Html<Strin
   let mut expression = input_str.to_string();
   expression = str::replace(&expression, "%5E", "^");
 	expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  // the equals sign, as it is recognized when solving the equation
+  expression = helper::equals_signs(&expression, &["x".to_string()]);
   for stri in ["div", "DIV", "d", "D"] {
     expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
   }
+  // Echo an equation as such, rather than as the function whose root is found.
+  let problem = if expression.contains('=') {
+    format!("the solution of the equation {}", str::replace(&expression, "X", "x"))
+  } else {
+    format!("the root of the function f(x) = {}", str::replace(&expression, "X", "x"))
+  };
+  // the function (or the difference between the equation's sides) near the root and the starting point
+  let function = helper::equation(input_str, &["x".to_string()]).unwrap_or(input_str.to_string());
+  let span = (result.x - result.xi).abs().max(1.);
+  let (left, right) = (result.x.min(result.xi) - span / 2., result.x.max(result.xi) + span / 2.);
+  let plot = svg::plot(&[
+    svg::Series {label: if expression.contains('=') {"left side - right side".to_string()} else {"f(x)".to_string()}, points: svg::graph(&function, left, right), style: svg::Style::Line},
+    svg::Series {label: format!("root at x = {}", result.x), points: vec![[result.x, 0.]], style: svg::Style::Dots},
+  ], "x", "f");
   content::Html(format!(
-    "{}<br><br><b>result</b>: {} is the root of the function f(x) = {}
+    "{}<br><br><b>result</b>: {} is {}
     which is found after starting from x = {}.<br>Bracketing the root
     required {} steps, and convergence to an absolute accuracy of {}
-    required {} more steps.",
+    required {} more steps.{}",
     instructions,
     result.x,
-    str::replace(&expression, "X", "x"),
+    problem,
     result.xi,
     result.bracket_steps,
     result.epsilon,
     result.root_steps,
+    plot,
   ))
 }
 
-#[get("/max-finding/<xi_str>/<input_str>")]
-fn max_finding_html(xi_str: &RawStr, input_str: &RawStr) -> content::Html<String> {
-  let instructions = max_finding::page();
-  let result = match max_finding::raw(xi_str, input_str) {
+// shared by max-finding and min-finding
+fn extremum_html(xi_str: &RawStr, input_str: &RawStr, mode: &str) -> content::Html<String> {
+  let instructions = if mode == "min" {max_finding::min_page()} else {max_finding::page()};
+  let result = match max_finding::raw(xi_str, input_str, mode) {
     Ok(result) => result,
     Err(message) => return content::Html(format!(
-      "{}<br><br><b>result</b> for the maximum of the
+      "{}<br><br><b>result</b> for the {}imum of the
       function f(x) = {} starting at x = {}:<br>{}",
       instructions,
+      mode,
       input_str,
       xi_str,
       message
@@ -239,36 +752,112 @@ fn max_finding_html(xi_str: &RawStr, input_str: &RawStr) -> content::Html<String
   for stri in ["div", "DIV", "d", "D"] {
     expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
   }
+  // the function near the extremum and the starting point
+  let span = (result.x - result.xi).abs().max(1.);
+  let (left, right) = (result.x.min(result.xi) - span / 2., result.x.max(result.xi) + span / 2.);
+  let plot = svg::plot(&[
+    svg::Series {label: "f(x)".to_string(), points: svg::graph(input_str, left, right), style: svg::Style::Line},
+    svg::Series {label: format!("{}imum at x = {}", result.mode, result.x), points: vec![[result.x, result.f]], style: svg::Style::Dots},
+  ], "x", "f");
   content::Html(format!(
     "{}<br><br><b>result</b>: ({}, {}) are the coordinates of the local
-    maximum of the function f(x) = {} which is found after starting from x = {}.<br>Bracketing the maximum required {} steps, and convergence to an absolute
-    accuracy of {} required {} more steps.",
+    {}imum of the function f(x) = {} which is found after starting from x = {}.<br>Bracketing the {}imum required {} steps, and convergence to a relative
+    accuracy of {} required {} more steps.{}",
     instructions,
     result.x,
     result.f,
+    result.mode,
     str::replace(&expression, "X", "x"),
     result.xi,
+    result.mode,
     result.bracket_steps,
     result.epsilon,
     result.max_steps,
+    plot,
   ))
 }
 
-#[get("/ode/<xi_str>/<tf_str>/<nt_str>/<input_str>")]
+#[get("/max-finding/<xi_str>/<input_str>?<mode>")]
+fn max_finding_html(xi_str: &RawStr, input_str: &RawStr, mode: Option<&RawStr>) -> content::Html<String> {
+  extremum_html(xi_str, input_str, mode.map_or("max", |mode| mode.as_str()))
+}
+
+#[get("/min-finding/<xi_str>/<input_str>?<mode>")]
+fn min_finding_html(xi_str: &RawStr, input_str: &RawStr, mode: Option<&RawStr>) -> content::Html<String> {
+  extremum_html(xi_str, input_str, mode.map_or("min", |mode| mode.as_str()))
+}
+
+// description of the steps taken by an adaptive integrator
+fn steps_summary(rtol: Option<f64>, atol: Option<f64>, accepted: i32, rejected: i32, switched: Option<f64>) -> String {
+  let summary = match (rtol, atol) {
+    (Some(rtol), Some(atol)) => format!(
+      "  With relative and absolute tolerances of {} and {}, the adaptive method
+      accepted {} steps and rejected {}.",
+      rtol,
+      atol,
+      accepted,
+      rejected,
+    ),
+    _ => "".to_string(),
+  };
+  match switched {
+    Some(t) => format!("{}  The equation was found to be stiff, so the stiff solver was used after t = {}.", summary, t),
+    None => summary,
+  }
+}
+
+// description of the events found while integrating
+fn events_summary(events: &[integrators::Event], terminal: Option<&RawStr>) -> String {
+  if events.is_empty() {
+    return "".to_string();
+  }
+  let mut summary = "  Events:".to_string();
+  for event in events {
+    summary = format!("{} {} = 0 at t = {} (where the variables equal {:?});", summary, event.event, event.t, event.y);
+  }
+  summary.pop();
+  summary.push('.');
+  if terminal.map_or(false, |terminal| terminal.as_str().to_lowercase() == "true") {
+    summary = format!("{}  The integration stopped at the first event.", summary);
+  }
+  summary
+}
+
+#[get("/ode/<xi_str>/<tf_str>/<nt_str>/<input_str>?<ti>&<method>&<rtol>&<atol>&<events>&<terminal>&<exact>")]
 fn ode_html(
   xi_str: &RawStr,
   tf_str: &RawStr,
   nt_str: &RawStr,
-  input_str: &RawStr
+  input_str: &RawStr,
+  ti: Option<&RawStr>,
+  method: Option<&RawStr>,
+  rtol: Option<&RawStr>,
+  atol: Option<&RawStr>,
+  events: Option<&RawStr>,
+  terminal: Option<&RawStr>,
+  exact: Option<&RawStr>,
 ) -> content::Html<String> {
   let instructions = ode::page();
-  let result = match ode::raw(xi_str, tf_str, nt_str, input_str) {
+  let result = match ode::raw(
+    xi_str,
+    tf_str,
+    nt_str,
+    input_str,
+    ti.map(|ti| ti.as_str()),
+    method.map(|method| method.as_str()),
+    rtol.map(|rtol| rtol.as_str()),
+    atol.map(|atol| atol.as_str()),
+    events.map(|events| events.as_str()),
+    terminal.map(|terminal| terminal.as_str()),
+    exact.map(|exact| exact.as_str()),
+  ) {
     Ok(result) => result,
     Err(message) => return content::Html(format!(
       "{}<br><br><b>result</b> for ODE that
-      dx/dt = {} if x(0) = {}:<br>{}",
+      dx/dt = {} if x({}) = {}:<br>{}",
       instructions,
       input_str,
+      ti.map_or("0", |ti| ti.as_str()),
       xi_str,
       message
     )),
@@ -279,68 +868,116 @@ fn ode_html(
   for stri in ["div", "DIV", "d", "D"] {
     expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
   }
+  // The global errors are shown only if the exact solution is known.
+  let columns = if result.errors.is_some() {3} else {2};
   let mut rows = "".to_string();
   for i in 0..result.xs.len() {
     rows = format!(
       "{}<div>{}</div><div>{}</div>",
       rows,
-      (i as f64) * result.tf / (result.nt as f64), result.xs[i],
+      result.ts[i], result.xs[i],
     );
+    if let Some(errors) = &result.errors {
+      rows = format!("{}<div>{}</div>", rows, errors[i]);
+    }
   }
   rows = format!("
   <div style='display: flex; flex-direction: column;'>
-    <div style='display: grid; grid-template-columns: repeat(2, 1fr); width:300px'>
+    <div style='display: grid; grid-template-columns: repeat({}, 1fr); width:{}px'>
       <div>
         <i>t</I>
       </div>
       <div>
         <i>x</i>
       </div>
+      {}
     </div>
     <div style='
       height:100px;
-      width:300px;
+      width:{}px;
       overflow-y:scroll;
       border-width:1px;
       border-style: solid;
       display: grid;
-      grid-template-columns: repeat(2, 1fr);
+      grid-template-columns: repeat({}, 1fr);
     '>
       {}
-    </div></div>", rows);
+    </div></div>",
+    columns,
+    150 * columns,
+    if result.errors.is_some() {"<div>error</div>"} else {""},
+    150 * columns,
+    columns,
+    rows,
+  );
   rows = format!(
     "<div style='display: flex;
     justify-content: center;'>{}</div>",
     rows,
   );
+  let mut series = vec![svg::Series {label: "x(t)".to_string(), points: result.ts.iter().zip(&result.xs).map(|(t, x)| [*t, *x]).collect(), style: svg::Style::Line}];
+  if let Some(errors) = &result.errors {
+    let points = result.ts.iter().zip(&result.xs).zip(errors).map(|((t, x), error)| [*t, x - error]).collect();
+    series.push(svg::Series {label: "exact".to_string(), points, style: svg::Style::Line});
+  }
   content::Html(format!(
     "{}<br><br><b>result</b>: Solution of the ODE dx/dt = {},
-    with the initial condition that x(0) = {}.<br>{}",
+    with the initial condition that x({}) = {}.{}{}<br>{}<br>{}",
     instructions,
     str::replace(&expression, "X", "x"),
+    result.ti,
     result.xi,
+    steps_summary(result.rtol, result.atol, result.accepted, result.rejected, result.switched),
+    events_summary(&result.events, terminal),
     rows,
+    svg::plot(&series, "t", "x"),
   ))
 }
 
-#[get("/ode2/<xi_str>/<vi_str>/<tf_str>/<nt_str>/<input_str>")]
+#[get("/ode2/<xi_str>/<vi_str>/<tf_str>/<nt_str>/<input_str>?<ti>&<method>&<rtol>&<atol>&<events>&<terminal>&<energy>&<period>&<lyapunov>")]
 fn ode2_html(
   xi_str: &RawStr,
   vi_str: &RawStr,
   tf_str: &RawStr,
   nt_str: &RawStr,
   input_str: &RawStr,
+  ti: Option<&RawStr>,
+  method: Option<&RawStr>,
+  rtol: Option<&RawStr>,
+  atol: Option<&RawStr>,
+  events: Option<&RawStr>,
+  terminal: Option<&RawStr>,
+  energy: Option<&RawStr>,
+  period: Option<&RawStr>,
+  lyapunov: Option<&RawStr>,
 ) -> content::Html<String> {
     let instructions = ode2::page();
-    let result = match ode2::raw(xi_str, vi_str, tf_str, nt_str, input_str) {
+    let result = match ode2::raw(
+      xi_str,
+      vi_str,
+      tf_str,
+      nt_str,
+      input_str,
+      ti.map(|ti| ti.as_str()),
+      method.map(|method| method.as_str()),
+      rtol.map(|rtol| rtol.as_str()),
+      atol.map(|atol| atol.as_str()),
+      events.map(|events| events.as_str()),
+      terminal.map(|terminal| terminal.as_str()),
+      energy.map(|energy| energy.as_str()),
+      period.map(|period| period.as_str()),
+      lyapunov.map(|lyapunov| lyapunov.as_str()),
+    ) {
       Ok(result) => result,
       Err(message) => return content::Html(format!(
         "{}<br><br><b>result</b> for 2nd-order ODE that
         d<sup>2</sup>x/dt<sup>2</sup> = {}
-        if x(0) = {} and v(0) = {}:<br>{}",
+        if x({}) = {} and v({}) = {}:<br>{}",
         instructions,
         input_str,
+        ti.map_or("0", |ti| ti.as_str()),
         xi_str,
+        ti.map_or("0", |ti| ti.as_str()),
         vi_str,
         message
       )),
@@ -358,7 +995,7 @@ fn ode2_html(
       rows = format!(
         "{}<div>{}</div><div>{}</div><div>{}</div>",
         rows,
-        (i as f64) * result.tf / (result.nt as f64), result.xs[i], result.vs[i],
+        result.ts[i], result.xs[i], result.vs[i],
       );
     }
     rows = format!("
@@ -386,20 +1023,1143 @@ fn ode2_html(
         {}
       </div></div>", rows);
     rows = format!("<div style='display: flex; justify-content: center;'>{}</div>", rows);
-    content::Html(format!("{}<br><br><b>result</b>: Solution of the ODE d<sup>2</sup>x/dt<sup>2</sup> = {}, with the initial conditions that x(0) = {} and that v(0) = {}.<br>{}",
+    // The Poincaré section is shown only if the period is specified.
+    if let Some(section) = &result.section {
+      let mut points = "".to_string();
+      for point in section {
+        points = format!("{}<div>{}</div><div>{}</div><div>{}</div>", points, point[0], point[1], point[2]);
+      }
+      rows = format!("{}<br><div style='display: flex; justify-content: center;'>
+      <div style='display: flex; flex-direction: column;'>
+        Poincaré section:
+        <div style='display: grid; grid-template-columns: repeat(3, 1fr); width:500px'>
+          <div><i>t</I></div><div><i>x</i></div><div><i>v</i></div>
+        </div>
+        <div style='
+          height:100px;
+          width:500px;
+          overflow-y:scroll;
+          border-width:1px;
+          border-style: solid;
+          display: grid;
+          grid-template-columns: repeat(3, 1fr);
+        '>
+          {}
+        </div></div></div>{}", rows, points, svg::plot(&[
+        svg::Series {label: "Poincaré section".to_string(), points: section.iter().map(|point| [point[1], point[2]]).collect(), style: svg::Style::Dots},
+      ], "x", "v"));
+    }
+    let plot = svg::plot(&[
+      svg::Series {label: "x(t)".to_string(), points: result.ts.iter().zip(&result.xs).map(|(t, x)| [*t, *x]).collect(), style: svg::Style::Line},
+      svg::Series {label: "v(t)".to_string(), points: result.ts.iter().zip(&result.vs).map(|(t, v)| [*t, *v]).collect(), style: svg::Style::Line},
+    ], "t", "x, v");
+    content::Html(format!("{}<br><br><b>result</b>: Solution of the ODE d<sup>2</sup>x/dt<sup>2</sup> = {}, with the initial conditions that x({}) = {} and that v({}) = {}.{}{}{}{}<br>{}<br>{}",
       instructions,
       str::replace(&expression, "X", "x"),
+      result.ti,
       result.xi,
+      result.ti,
       result.vi,
+      steps_summary(result.rtol, result.atol, result.accepted, result.rejected, result.switched),
+      events_summary(&result.events, terminal),
+      match result.drift {
+        Some(drift) => format!("  The energy's largest deviation from its initial value was {}.", drift),
+        None => "".to_string(),
+      },
+      match result.lyapunov {
+        Some(lyapunov) => format!("  The estimate of the largest Lyapunov exponent is {}.", lyapunov),
+        None => "".to_string(),
+      },
       rows,
+      plot,
     ))
 }
 
+#[get("/nonlinear-systems/<vars_str>/<xi_str>/<input_str>")]
+fn nonlinear_systems_html(vars_str: &RawStr, xi_str: &RawStr, input_str: &RawStr) -> content::Html<String> {
+  let instructions = nonlinear_systems::page();
+  let result = match nonlinear_systems::raw(vars_str, xi_str, input_str) {
+    Ok(result) => result,
+    Err(message) => return content::Html(format!(
+      "{}<br><br><b>result</b> for solving the equations {} for the
+      unknowns {} after starting at {}:<br>{}",
+      instructions,
+      input_str,
+      vars_str,
+      xi_str,
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+	expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  // the equals signs, as they are recognized when solving the equations
+  expression = helper::equals_signs(&expression, &result.vars);
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  // Functions (as opposed to equations) are to be set equal to zero.
+  let equations: Vec<String> = expression.split(',').map(|equation| {
+    if equation.contains('=') {equation.to_string()} else {format!("{} = 0", equation)}
+  }).collect();
+  let mut rows = "".to_string();
+  for i in 0..result.vars.len() {
+    rows = format!("{}<li>{} = {}</li>", rows, result.vars[i], result.x[i]);
+  }
+  content::Html(format!(
+    "{}<br><br><b>result</b>: the solution of the equations {},
+    which is found after starting from {:?}, is<ul>{}</ul>The root-mean-square
+    residual there is {}.  Convergence to an accuracy of {} required {} steps
+    and {} calculation(s) of the Jacobian matrix.",
+    instructions,
+    equations.join(", "),
+    result.xi,
+    rows,
+    result.residual,
+    result.epsilon,
+    result.steps,
+    result.jacobians,
+  ))
+}
+
+#[get("/global-max/<a_str>/<b_str>/<input_str>")]
+fn global_max_html(a_str: &RawStr, b_str: &RawStr, input_str: &RawStr) -> content::Html<String> {
+  let instructions = global_max::page();
+  let result = match global_max::raw(a_str, b_str, input_str) {
+    Ok(result) => result,
+    Err(message) => return content::Html(format!(
+      "{}<br><br><b>result</b> for the global extrema of the
+      function f(x) = {} from x = {} to x = {}:<br>{}",
+      instructions,
+      input_str,
+      a_str,
+      b_str,
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+	expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  let mut rows = "".to_string();
+  for extremum in &result.extrema {
+    rows = format!(
+      "{}<li>local {}imum at ({}, {}){}</li>",
+      rows,
+      extremum.kind,
+      extremum.x,
+      extremum.f,
+      if extremum.endpoint {" (endpoint)"} else {""},
+    );
+  }
+  content::Html(format!(
+    "{}<br><br><b>result</b>: Over the interval from x = {} to x = {}, the
+    global maximum of the function f(x) = {} is at ({}, {}), and its global
+    minimum is at ({}, {}).<br>The local extrema are<ul>{}</ul>",
+    instructions,
+    result.a,
+    result.b,
+    str::replace(&expression, "X", "x"),
+    result.max.x,
+    result.max.f,
+    result.min.x,
+    result.min.f,
+    rows,
+  ))
+}
+
+#[get("/optimization/<vars_str>/<xi_str>/<input_str>?<method>&<mode>")]
+fn optimization_html(
+  vars_str: &RawStr,
+  xi_str: &RawStr,
+  input_str: &RawStr,
+  method: Option<&RawStr>,
+  mode: Option<&RawStr>,
+) -> content::Html<String> {
+  let instructions = optimization::page();
+  let mode = mode.map_or("min", |mode| mode.as_str());
+  let result = match optimization::raw(
+    vars_str,
+    xi_str,
+    input_str,
+    method.map_or("bfgs", |method| method.as_str()),
+    mode,
+  ) {
+    Ok(result) => result,
+    Err(message) => return content::Html(format!(
+      "{}<br><br><b>result</b> for the {}imum of the function f({}) = {}
+      starting at {}:<br>{}",
+      instructions,
+      mode,
+      vars_str,
+      input_str,
+      xi_str,
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+	expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  let mut rows = "".to_string();
+  for i in 0..result.vars.len() {
+    rows = format!("{}<li>{} = {}</li>", rows, result.vars[i], result.x[i]);
+  }
+  content::Html(format!(
+    "{}<br><br><b>result</b>: the local {}imum of the function f({}) = {},
+    which is found with the {} method after starting from {:?}, is f = {} at<ul>{}</ul>The
+    magnitude of the gradient there is {}.  Convergence to a relative accuracy
+    of {} required {} steps.",
+    instructions,
+    result.mode,
+    result.vars.join(", "),
+    expression,
+    result.method,
+    result.xi,
+    result.f,
+    rows,
+    result.gradient_norm,
+    result.epsilon,
+    result.steps,
+  ))
+}
+
+#[get("/constrained-optimization/<vars_str>/<xi_str>/<constraints_str>/<input_str>?<mode>&<lower>&<upper>")]
+fn constrained_optimization_html(
+  vars_str: &RawStr,
+  xi_str: &RawStr,
+  constraints_str: &RawStr,
+  input_str: &RawStr,
+  mode: Option<&RawStr>,
+  lower: Option<&RawStr>,
+  upper: Option<&RawStr>,
+) -> content::Html<String> {
+  let instructions = constrained_optimization::page();
+  let mode = mode.map_or("min", |mode| mode.as_str());
+  let result = match constrained_optimization::raw(
+    vars_str,
+    xi_str,
+    constraints_str,
+    input_str,
+    mode,
+    lower.map(|lower| lower.as_str()),
+    upper.map(|upper| upper.as_str()),
+  ) {
+    Ok(result) => result,
+    Err(message) => return content::Html(format!(
+      "{}<br><br><b>result</b> for the {}imum of the function f({}) = {}
+      subject to the constraints {} starting at {}:<br>{}",
+      instructions,
+      mode,
+      vars_str,
+      input_str,
+      constraints_str,
+      xi_str,
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+	expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  let mut rows = "".to_string();
+  for i in 0..result.vars.len() {
+    rows = format!("{}<li>{} = {}</li>", rows, result.vars[i], result.x[i]);
+  }
+  let mut constraints = "".to_string();
+  for constraint in &result.constraints {
+    constraints = format!(
+      "{}<li>{}: {}{}</li>",
+      constraints,
+      constraint.constraint,
+      if constraint.active {"active, with Lagrange multiplier "} else {"inactive"},
+      if constraint.active {constraint.multiplier.to_string()} else {"".to_string()},
+    );
+  }
+  content::Html(format!(
+    "{}<br><br><b>result</b>: the constrained local {}imum of the function f({}) = {},
+    which is found after starting from {:?}, is f = {} at<ul>{}</ul>The constraints are
+    <ul>{}</ul>The constraints are satisfied to within {}, which required {} updates of the
+    Lagrange multipliers.",
+    instructions,
+    result.mode,
+    result.vars.join(", "),
+    expression,
+    result.xi,
+    result.f,
+    rows,
+    constraints,
+    result.violation,
+    result.steps,
+  ))
+}
+
+#[get("/analyze/<a_str>/<b_str>/<input_str>")]
+fn analysis_html(a_str: &RawStr, b_str: &RawStr, input_str: &RawStr) -> content::Html<String> {
+  let instructions = analysis::page();
+  let result = match analysis::raw(a_str, b_str, input_str) {
+    Ok(result) => result,
+    Err(message) => return content::Html(format!(
+      "{}<br><br><b>result</b> for the analysis of the
+      function f(x) = {} from x = {} to x = {}:<br>{}",
+      instructions,
+      input_str,
+      a_str,
+      b_str,
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+  expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  let zeros = if result.zeros.is_empty() {
+    "none".to_string()
+  } else {
+    result.zeros.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(", ")
+  };
+  let mut extrema = "".to_string();
+  for extremum in &result.extrema {
+    extrema = format!(
+      "{}<li>local {}imum at ({}, {}){}</li>",
+      extrema,
+      extremum.kind,
+      extremum.x,
+      extremum.f,
+      if extremum.endpoint {" (endpoint)"} else {""},
+    );
+  }
+  let mut inflections = "".to_string();
+  for point in &result.inflections {
+    inflections = format!("{}<li>({}, {})</li>", inflections, point.x, point.f);
+  }
+  if inflections.is_empty() {
+    inflections = "<li>none</li>".to_string();
+  }
+  let mut intervals = "".to_string();
+  for interval in result.monotonicity.iter().chain(result.concavity.iter()) {
+    intervals = format!("{}<li>{} from x = {} to x = {}</li>", intervals, interval.behavior, interval.from, interval.to);
+  }
+  content::Html(format!(
+    "{}<br><br><b>result</b>: Over the interval from x = {} to x = {}, the
+    zeros of the function f(x) = {} are at x = {}.<br>The local extrema are<ul>{}</ul>The
+    inflection points are<ul>{}</ul>The function is<ul>{}</ul>",
+    instructions,
+    result.a,
+    result.b,
+    str::replace(&expression, "X", "x"),
+    zeros,
+    extrema,
+    inflections,
+    intervals,
+  ))
+}
+
+#[get("/odes/<vars_str>/<xi_str>/<tf_str>/<nt_str>/<input_str>?<ti>&<method>&<rtol>&<atol>&<events>&<terminal>")]
+fn odes_html(
+  vars_str: &RawStr,
+  xi_str: &RawStr,
+  tf_str: &RawStr,
+  nt_str: &RawStr,
+  input_str: &RawStr,
+  ti: Option<&RawStr>,
+  method: Option<&RawStr>,
+  rtol: Option<&RawStr>,
+  atol: Option<&RawStr>,
+  events: Option<&RawStr>,
+  terminal: Option<&RawStr>,
+) -> content::Html<String> {
+  let instructions = odes::page();
+  let result = match odes::raw(
+    vars_str,
+    xi_str,
+    tf_str,
+    nt_str,
+    input_str,
+    ti.map(|ti| ti.as_str()),
+    method.map(|method| method.as_str()),
+    rtol.map(|rtol| rtol.as_str()),
+    atol.map(|atol| atol.as_str()),
+    events.map(|events| events.as_str()),
+    terminal.map(|terminal| terminal.as_str()),
+  ) {
+    Ok(result) => result,
+    Err(message) => return content::Html(format!(
+      "{}<br><br><b>result</b> for the ODEs that d({})/dt = ({})
+      if ({}) = ({}) at t = {}:<br>{}",
+      instructions,
+      vars_str,
+      input_str,
+      vars_str,
+      xi_str,
+      ti.map_or("0", |ti| ti.as_str()),
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+  expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  expression = str::replace(&expression, "%2C", ",");
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  let equations: Vec<String> = result.vars.iter().zip(expression.split(',')).map(|(var, function)| {
+    format!("d{}/dt = {}", var, function)
+  }).collect();
+  let initial: Vec<String> = result.vars.iter().zip(&result.xi).map(|(var, x)| {
+    format!("{}({}) = {}", var, result.ti, x)
+  }).collect();
+  let columns = result.vars.len() + 1;
+  let mut headings = "<div><i>t</i></div>".to_string();
+  for var in &result.vars {
+    headings = format!("{}<div><i>{}</i></div>", headings, var);
+  }
+  let mut rows = "".to_string();
+  for (t, y) in result.ts.iter().zip(&result.ys) {
+    rows = format!("{}<div>{}</div>", rows, t);
+    for value in y {
+      rows = format!("{}<div>{}</div>", rows, value);
+    }
+  }
+  rows = format!("
+  <div style='display: flex; flex-direction: column;'>
+    <div style='display: grid; grid-template-columns: repeat({}, 1fr); width:{}px'>
+      {}
+    </div>
+    <div style='
+      height:100px;
+      width:{}px;
+      overflow-y:scroll;
+      border-width:1px;
+      border-style: solid;
+      display: grid;
+      grid-template-columns: repeat({}, 1fr);
+    '>
+      {}
+    </div></div>", columns, 150 * columns, headings, 150 * columns, columns, rows);
+  rows = format!("<div style='display: flex; justify-content: center;'>{}</div>", rows);
+  let series: Vec<svg::Series> = result.vars.iter().enumerate().map(|(j, var)| svg::Series {
+    label: format!("{}(t)", var),
+    points: result.ts.iter().zip(&result.ys).map(|(t, y)| [*t, y[j]]).collect(),
+    style: svg::Style::Line,
+  }).collect();
+  content::Html(format!(
+    "{}<br><br><b>result</b>: Solution of the ODEs {}, with the initial
+    conditions that {}.{}{}<br>{}<br>{}",
+    instructions,
+    equations.join(", "),
+    initial.join(", "),
+    steps_summary(result.rtol, result.atol, result.accepted, result.rejected, result.switched),
+    events_summary(&result.events, terminal),
+    rows,
+    svg::plot(&series, "t", &result.vars.join(", ")),
+  ))
+}
+
+#[get("/bvp/<xi_str>/<xf_str>/<tf_str>/<nt_str>/<input_str>?<ti>&<method>")]
+fn bvp_html(
+  xi_str: &RawStr,
+  xf_str: &RawStr,
+  tf_str: &RawStr,
+  nt_str: &RawStr,
+  input_str: &RawStr,
+  ti: Option<&RawStr>,
+  method: Option<&RawStr>,
+) -> content::Html<String> {
+  let instructions = bvp::page();
+  let result = match bvp::raw(xi_str, xf_str, tf_str, nt_str, input_str, ti.map(|ti| ti.as_str()), method.map(|method| method.as_str())) {
+    Ok(result) => result,
+    Err(message) => return content::Html(format!(
+      "{}<br><br><b>result</b> for the ODE that
+      d<sup>2</sup>x/dt<sup>2</sup> = {}
+      if x({}) = {} and x({}) = {}:<br>{}",
+      instructions,
+      input_str,
+      ti.map_or("0", |ti| ti.as_str()),
+      xi_str,
+      tf_str,
+      xf_str,
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+  expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  let mut rows = "".to_string();
+  for i in 0..result.xs.len() {
+    rows = format!(
+      "{}<div>{}</div><div>{}</div><div>{}</div>",
+      rows,
+      result.ts[i], result.xs[i], result.vs[i],
+    );
+  }
+  rows = format!("
+  <div style='display: flex; flex-direction: column;'>
+    <div style='display: grid; grid-template-columns: repeat(3, 1fr); width:500px'>
+      <div>
+        <i>t</I>
+      </div>
+      <div>
+        <i>x</i>
+      </div>
+      <div>
+        <i>v</i>
+      </div>
+    </div>
+    <div style='
+      height:100px;
+      width:500px;
+      overflow-y:scroll;
+      border-width:1px;
+      border-style: solid;
+      display: grid;
+      grid-template-columns: repeat(3, 1fr);
+    '>
+      {}
+    </div></div>", rows);
+  rows = format!("<div style='display: flex; justify-content: center;'>{}</div>", rows);
+  content::Html(format!(
+    "{}<br><br><b>result</b>: Solution of the ODE d<sup>2</sup>x/dt<sup>2</sup> = {},
+    with the boundary conditions that x({}) = {} and x({}) = {}, found by the {} method
+    after {} iterations.<br>{}",
+    instructions,
+    str::replace(&expression, "X", "x"),
+    result.ti,
+    result.xi,
+    result.tf,
+    result.xf,
+    result.method,
+    result.steps,
+    rows,
+  ))
+}
+
+#[get("/sturm-liouville/<a_str>/<b_str>/<n_str>/<q_str>?<w>&<left>&<right>&<nx>")]
+fn sturm_liouville_html(
+  a_str: &RawStr,
+  b_str: &RawStr,
+  n_str: &RawStr,
+  q_str: &RawStr,
+  w: Option<&RawStr>,
+  left: Option<&RawStr>,
+  right: Option<&RawStr>,
+  nx: Option<&RawStr>,
+) -> content::Html<String> {
+  let instructions = sturm_liouville::page();
+  let result = match sturm_liouville::raw(
+    a_str,
+    b_str,
+    n_str,
+    q_str,
+    w.map(|w| w.as_str()),
+    left.map(|left| left.as_str()),
+    right.map(|right| right.as_str()),
+    nx.map(|nx| nx.as_str()),
+  ) {
+    Ok(result) => result,
+    Err(message) => return content::Html(format!(
+      "{}<br><br><b>result</b> for the eigenvalues of -y'' + ({})y = &lambda;({})y
+      over the interval from x = {} to {}:<br>{}",
+      instructions,
+      q_str,
+      w.map_or("1", |w| w.as_str()),
+      a_str,
+      b_str,
+      message
+    )),
+  };
+  let mut expression = q_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+  expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  let columns = result.eigenvalues.len() + 1;
+  let mut headings = "<div><i>x</i></div>".to_string();
+  for k in 1..columns {
+    headings = format!("{}<div><i>y</i><sub>{}</sub></div>", headings, k);
+  }
+  let mut rows = "".to_string();
+  for (i, x) in result.xs.iter().enumerate() {
+    rows = format!("{}<div>{}</div>", rows, x);
+    for eigenfunction in &result.eigenfunctions {
+      rows = format!("{}<div>{}</div>", rows, eigenfunction[i]);
+    }
+  }
+  rows = format!("
+  <div style='display: flex; flex-direction: column;'>
+    <div style='display: grid; grid-template-columns: repeat({}, 1fr); width:{}px'>
+      {}
+    </div>
+    <div style='
+      height:100px;
+      width:{}px;
+      overflow-y:scroll;
+      border-width:1px;
+      border-style: solid;
+      display: grid;
+      grid-template-columns: repeat({}, 1fr);
+    '>
+      {}
+    </div></div>", columns, 150 * columns, headings, 150 * columns, columns, rows);
+  rows = format!("<div style='display: flex; justify-content: center;'>{}</div>", rows);
+  let eigenvalues: Vec<String> = result.eigenvalues.iter().map(|lambda| lambda.to_string()).collect();
+  content::Html(format!(
+    "{}<br><br><b>result</b>: The eigenvalues of -y'' + ({})y = &lambda;({})y over the interval
+    from x = {} ({}) to {} ({}) are {}.  The normalized eigenfunctions are below.<br>{}",
+    instructions,
+    str::replace(&expression, "X", "x"),
+    w.map_or("1", |w| w.as_str()),
+    result.a,
+    result.left,
+    result.b,
+    result.right,
+    eigenvalues.join(", "),
+    rows,
+  ))
+}
+
+#[get("/oden/<order_str>/<xi_str>/<tf_str>/<nt_str>/<input_str>?<ti>&<method>&<rtol>&<atol>&<events>&<terminal>")]
+fn oden_html(
+  order_str: &RawStr,
+  xi_str: &RawStr,
+  tf_str: &RawStr,
+  nt_str: &RawStr,
+  input_str: &RawStr,
+  ti: Option<&RawStr>,
+  method: Option<&RawStr>,
+  rtol: Option<&RawStr>,
+  atol: Option<&RawStr>,
+  events: Option<&RawStr>,
+  terminal: Option<&RawStr>,
+) -> content::Html<String> {
+  let instructions = oden::page();
+  let result = match oden::raw(
+    order_str,
+    xi_str,
+    tf_str,
+    nt_str,
+    input_str,
+    ti.map(|ti| ti.as_str()),
+    method.map(|method| method.as_str()),
+    rtol.map(|rtol| rtol.as_str()),
+    atol.map(|atol| atol.as_str()),
+    events.map(|events| events.as_str()),
+    terminal.map(|terminal| terminal.as_str()),
+  ) {
+    Ok(result) => result,
+    Err(message) => return content::Html(format!(
+      "{}<br><br><b>result</b> for the ODE of order {} that
+      d<sup>{}</sup>x/dt<sup>{}</sup> = {} if (x, x1, ...) = ({}) at t = {}:<br>{}",
+      instructions,
+      order_str,
+      order_str,
+      order_str,
+      input_str,
+      xi_str,
+      ti.map_or("0", |ti| ti.as_str()),
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+  expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  let names: Vec<String> = (0..result.order).map(|j| if j == 0 {"x".to_string()} else {format!("x<sub>{}</sub>", j)}).collect();
+  let initial: Vec<String> = names.iter().zip(&result.xi).map(|(name, x)| {
+    format!("{}({}) = {}", name, result.ti, x)
+  }).collect();
+  let columns = result.order + 1;
+  let mut headings = "<div><i>t</i></div>".to_string();
+  for name in &names {
+    headings = format!("{}<div><i>{}</i></div>", headings, name);
+  }
+  let mut rows = "".to_string();
+  for (t, x) in result.ts.iter().zip(&result.xs) {
+    rows = format!("{}<div>{}</div>", rows, t);
+    for value in x {
+      rows = format!("{}<div>{}</div>", rows, value);
+    }
+  }
+  rows = format!("
+  <div style='display: flex; flex-direction: column;'>
+    <div style='display: grid; grid-template-columns: repeat({}, 1fr); width:{}px'>
+      {}
+    </div>
+    <div style='
+      height:100px;
+      width:{}px;
+      overflow-y:scroll;
+      border-width:1px;
+      border-style: solid;
+      display: grid;
+      grid-template-columns: repeat({}, 1fr);
+    '>
+      {}
+    </div></div>", columns, 150 * columns, headings, 150 * columns, columns, rows);
+  rows = format!("<div style='display: flex; justify-content: center;'>{}</div>", rows);
+  let series: Vec<svg::Series> = (0..result.order).map(|j| svg::Series {
+    label: if j == 0 {"x(t)".to_string()} else {format!("x{}(t)", j)},
+    points: result.ts.iter().zip(&result.xs).map(|(t, x)| [*t, x[j]]).collect(),
+    style: svg::Style::Line,
+  }).collect();
+  content::Html(format!(
+    "{}<br><br><b>result</b>: Solution of the ODE d<sup>{}</sup>x/dt<sup>{}</sup> = {}, with the initial
+    conditions that {}.{}{}<br>{}<br>{}",
+    instructions,
+    result.order,
+    result.order,
+    str::replace(&expression, "X", "x"),
+    initial.join(", "),
+    steps_summary(result.rtol, result.atol, result.accepted, result.rejected, result.switched),
+    events_summary(&result.events, terminal),
+    rows,
+    svg::plot(&series, "t", "x"),
+  ))
+}
+
+#[get("/dde/<delays_str>/<history_str>/<tf_str>/<nt_str>/<input_str>?<ti>")]
+fn dde_html(
+  delays_str: &RawStr,
+  history_str: &RawStr,
+  tf_str: &RawStr,
+  nt_str: &RawStr,
+  input_str: &RawStr,
+  ti: Option<&RawStr>,
+) -> content::Html<String> {
+  let instructions = dde::page();
+  let result = match dde::raw(delays_str, history_str, tf_str, nt_str, input_str, ti.map(|ti| ti.as_str())) {
+    Ok(result) => result,
+    Err(message) => return content::Html(format!(
+      "{}<br><br><b>result</b> for the DDE that
+      dx/dt = {} with delays {} if x = {} for t &le; {}:<br>{}",
+      instructions,
+      input_str,
+      delays_str,
+      history_str,
+      ti.map_or("0", |ti| ti.as_str()),
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+  expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  let mut rows = "".to_string();
+  for i in 0..result.xs.len() {
+    rows = format!(
+      "{}<div>{}</div><div>{}</div>",
+      rows,
+      result.ts[i], result.xs[i],
+    );
+  }
+  rows = format!("
+  <div style='display: flex; flex-direction: column;'>
+    <div style='display: grid; grid-template-columns: repeat(2, 1fr); width:300px'>
+      <div>
+        <i>t</I>
+      </div>
+      <div>
+        <i>x</i>
+      </div>
+    </div>
+    <div style='
+      height:100px;
+      width:300px;
+      overflow-y:scroll;
+      border-width:1px;
+      border-style: solid;
+      display: grid;
+      grid-template-columns: repeat(2, 1fr);
+    '>
+      {}
+    </div></div>", rows);
+  rows = format!("<div style='display: flex; justify-content: center;'>{}</div>", rows);
+  let delays: Vec<String> = result.delays.iter().map(|delay| delay.to_string()).collect();
+  content::Html(format!(
+    "{}<br><br><b>result</b>: Solution of the DDE dx/dt = {}, with the delays {}
+    and the history that x = {} for t &le; {}.<br>{}",
+    instructions,
+    str::replace(&expression, "X", "x"),
+    delays.join(", "),
+    history_str,
+    result.ti,
+    rows,
+  ))
+}
+
+#[get("/sde/<xi_str>/<tf_str>/<nt_str>/<drift_str>/<diffusion_str>?<ti>&<method>&<seed>&<paths>")]
+fn sde_html(
+  xi_str: &RawStr,
+  tf_str: &RawStr,
+  nt_str: &RawStr,
+  drift_str: &RawStr,
+  diffusion_str: &RawStr,
+  ti: Option<&RawStr>,
+  method: Option<&RawStr>,
+  seed: Option<&RawStr>,
+  paths: Option<&RawStr>,
+) -> content::Html<String> {
+  let instructions = sde::page();
+  let result = match sde::raw(
+    xi_str,
+    tf_str,
+    nt_str,
+    drift_str,
+    diffusion_str,
+    ti.map(|ti| ti.as_str()),
+    method.map(|method| method.as_str()),
+    seed.map(|seed| seed.as_str()),
+    paths.map(|paths| paths.as_str()),
+  ) {
+    Ok(result) => result,
+    Err(message) => return content::Html(format!(
+      "{}<br><br><b>result</b> for the SDE that
+      dx = ({})dt + ({})dW if x({}) = {}:<br>{}",
+      instructions,
+      drift_str,
+      diffusion_str,
+      ti.map_or("0", |ti| ti.as_str()),
+      xi_str,
+      message
+    )),
+  };
+  let mut expressions = vec![];
+  for input_str in [drift_str, diffusion_str] {
+    let mut expression = input_str.to_string();
+    expression = str::replace(&expression, "%5E", "^");
+    expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+    for stri in ["div", "DIV", "d", "D"] {
+      expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+    }
+    expressions.push(str::replace(&expression, "X", "x"));
+  }
+  // A single path is shown, or else the mean and variance of the ensemble.
+  let columns = if result.paths == 1 {2} else {3};
+  let mut rows = "".to_string();
+  for i in 0..result.ts.len() {
+    rows = format!("{}<div>{}</div>", rows, result.ts[i]);
+    if let Some(xs) = &result.xs {
+      rows = format!("{}<div>{}</div>", rows, xs[i]);
+    }
+    if let (Some(means), Some(variances)) = (&result.means, &result.variances) {
+      rows = format!("{}<div>{}</div><div>{}</div>", rows, means[i], variances[i]);
+    }
+  }
+  rows = format!("
+  <div style='display: flex; flex-direction: column;'>
+    <div style='display: grid; grid-template-columns: repeat({}, 1fr); width:{}px'>
+      <div>
+        <i>t</I>
+      </div>
+      {}
+    </div>
+    <div style='
+      height:100px;
+      width:{}px;
+      overflow-y:scroll;
+      border-width:1px;
+      border-style: solid;
+      display: grid;
+      grid-template-columns: repeat({}, 1fr);
+    '>
+      {}
+    </div></div>",
+    columns,
+    150 * columns,
+    if result.paths == 1 {"<div><i>x</i></div>"} else {"<div>mean</div><div>variance</div>"},
+    150 * columns,
+    columns,
+    rows,
+  );
+  rows = format!("<div style='display: flex; justify-content: center;'>{}</div>", rows);
+  content::Html(format!(
+    "{}<br><br><b>result</b>: Solution of the SDE dx = ({})dt + ({})dW, with x({}) = {}, by the {} method with the seed {}{}.<br>{}",
+    instructions,
+    expressions[0],
+    expressions[1],
+    result.ti,
+    result.xi,
+    result.method,
+    result.seed,
+    if result.paths == 1 {"".to_string()} else {format!(", for an ensemble of {} paths", result.paths)},
+    rows,
+  ))
+}
+
+#[get("/pde/<equation_str>/<coefficient_str>/<a_str>/<b_str>/<nx_str>/<initial_str>/<tf_str>/<nt_str>?<source>&<velocity>&<left>&<right>&<ti>&<method>&<rtol>&<atol>")]
+fn pde_html(
+  equation_str: &RawStr,
+  coefficient_str: &RawStr,
+  a_str: &RawStr,
+  b_str: &RawStr,
+  nx_str: &RawStr,
+  initial_str: &RawStr,
+  tf_str: &RawStr,
+  nt_str: &RawStr,
+  source: Option<&RawStr>,
+  velocity: Option<&RawStr>,
+  left: Option<&RawStr>,
+  right: Option<&RawStr>,
+  ti: Option<&RawStr>,
+  method: Option<&RawStr>,
+  rtol: Option<&RawStr>,
+  atol: Option<&RawStr>,
+) -> content::Html<String> {
+  let instructions = pde::page();
+  let result = match pde::raw(
+    equation_str,
+    coefficient_str,
+    a_str,
+    b_str,
+    nx_str,
+    initial_str,
+    tf_str,
+    nt_str,
+    source.map(|source| source.as_str()),
+    velocity.map(|velocity| velocity.as_str()),
+    left.map(|left| left.as_str()),
+    right.map(|right| right.as_str()),
+    ti.map(|ti| ti.as_str()),
+    method.map(|method| method.as_str()),
+    rtol.map(|rtol| rtol.as_str()),
+    atol.map(|atol| atol.as_str()),
+  ) {
+    Ok(result) => result,
+    Err(message) => return content::Html(format!(
+      "{}<br><br><b>result</b> for the {} equation with the coefficient {} if u = {} at t = {}:<br>{}",
+      instructions,
+      equation_str,
+      coefficient_str,
+      initial_str,
+      ti.map_or("0", |ti| ti.as_str()),
+      message
+    )),
+  };
+  let columns = result.xs.len() + 1;
+  let mut rows = "<div><i>t</i> &#92; <i>x</i></div>".to_string();
+  for x in &result.xs {
+    rows = format!("{}<div><i>{}</i></div>", rows, x);
+  }
+  for (t, us) in result.ts.iter().zip(&result.us) {
+    rows = format!("{}<div><i>{}</i></div>", rows, t);
+    for u in us {
+      rows = format!("{}<div>{}</div>", rows, u);
+    }
+  }
+  rows = format!("
+  <div style='
+    height:150px;
+    width:600px;
+    overflow:scroll;
+    border-width:1px;
+    border-style: solid;
+    display: grid;
+    grid-template-columns: repeat({}, 150px);
+  '>
+    {}
+  </div>", columns, rows);
+  rows = format!("<div style='display: flex; justify-content: center;'>{}</div>", rows);
+  content::Html(format!(
+    "{}<br><br><b>result</b>: Solution of the {} equation with the {} {}, with {} and {} conditions at x = {} and {}, by the {} method.{}<br>{}<br>{}",
+    instructions,
+    result.equation,
+    if result.equation == "heat" {"diffusivity"} else {"speed"},
+    result.coefficient,
+    result.left,
+    result.right,
+    result.a,
+    result.b,
+    result.method,
+    steps_summary(result.rtol, result.atol, result.accepted, result.rejected, result.switched),
+    rows,
+    format!("<div style='display: flex; justify-content: center;'><div>{}</div></div>", svg::heat_map(&result.xs, &result.ts, &result.us)),
+  ))
+}
+
+#[get("/direction-field/<equation_str>/<left_str>/<right_str>/<bottom_str>/<top_str>/<input_str>?<initial>&<n>&<ti>&<tf>&<nt>&<method>")]
+fn direction_field_html(
+  equation_str: &RawStr,
+  left_str: &RawStr,
+  right_str: &RawStr,
+  bottom_str: &RawStr,
+  top_str: &RawStr,
+  input_str: &RawStr,
+  initial: Option<&RawStr>,
+  n: Option<&RawStr>,
+  ti: Option<&RawStr>,
+  tf: Option<&RawStr>,
+  nt: Option<&RawStr>,
+  method: Option<&RawStr>,
+) -> content::Html<String> {
+  let instructions = direction_field::page();
+  let result = match direction_field::raw(
+    equation_str,
+    left_str,
+    right_str,
+    bottom_str,
+    top_str,
+    input_str,
+    initial.map(|initial| initial.as_str()),
+    n.map(|n| n.as_str()),
+    ti.map(|ti| ti.as_str()),
+    tf.map(|tf| tf.as_str()),
+    nt.map(|nt| nt.as_str()),
+    method.map(|method| method.as_str()),
+  ) {
+    Ok(result) => result,
+    Err(message) => return content::Html(format!(
+      "{}<br><br><b>result</b> for the {} equation whose function is {}:<br>{}",
+      instructions,
+      equation_str,
+      input_str,
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+  expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  expression = str::replace(&expression, "X", "x");
+  let equation = if result.equation == "ode" {
+    format!("direction field of the equation dx/dt = {}", expression)
+  } else {
+    format!("phase portrait of the equation d<sup>2</sup>x/dt<sup>2</sup> = {}", expression)
+  };
+  let mut trajectories = "".to_string();
+  for (k, initial) in result.initial.iter().enumerate() {
+    let initial: Vec<String> = initial.iter().map(|value| value.to_string()).collect();
+    trajectories = format!("{}<br><span style='color: {};'>&#9632;</span> trajectory from ({})", trajectories, svg::color(k), initial.join(", "));
+  }
+  content::Html(format!(
+    "{}<br><br><b>result</b>: The {} for {} &lt; {} &lt; {} and {} &lt; {} &lt; {}.{}
+    <div style='display: flex; justify-content: center;'>{}</div>",
+    instructions,
+    equation,
+    result.left,
+    if result.equation == "ode" {"t"} else {"x"},
+    result.right,
+    result.bottom,
+    if result.equation == "ode" {"x"} else {"v"},
+    result.top,
+    trajectories,
+    result.svg,
+  ))
+}
+
+#[get("/table/<a_str>/<b_str>/<n_str>/<input_str>?<spacing>")]
+fn table_html(
+  a_str: &RawStr,
+  b_str: &RawStr,
+  n_str: &RawStr,
+  input_str: &RawStr,
+  spacing: Option<&RawStr>,
+) -> content::Html<String> {
+  let instructions = table::page();
+  let result = match table::raw(a_str, b_str, n_str, input_str, spacing.map(|spacing| spacing.as_str())) {
+    Ok(result) => result,
+    Err(message) => return content::Html(format!(
+      "{}<br><br><b>result</b> for the table of the function f(x) = {} from x = {} to x = {}:<br>{}",
+      instructions,
+      input_str,
+      a_str,
+      b_str,
+      message
+    )),
+  };
+  let mut expression = input_str.to_string();
+  expression = str::replace(&expression, "%5E", "^");
+  expression = str::replace(&expression, "%20", ""); // %20 is url encoding of space
+  for stri in ["div", "DIV", "d", "D"] {
+    expression = str::replace(&expression, stri, "/"); // division operation is a special URL char
+  }
+  // A point at which the function could not be evaluated shows the error instead of a value.
+  let mut rows = "".to_string();
+  for point in &result.points {
+    rows = format!("{}<div>{}</div><div>{}</div>", rows, point.x, match (&point.f, &point.error) {
+      (Some(f), _) => f.to_string(),
+      (None, Some(error)) => error.to_string(),
+      (None, None) => "".to_string(),
+    });
+  }
+  rows = format!("
+  <div style='display: flex; flex-direction: column;'>
+    <div style='display: grid; grid-template-columns: 1fr 2fr; width:450px'>
+      <div>
+        <i>x</I>
+      </div>
+      <div>
+        <i>f</I>
+      </div>
+    </div>
+    <div style='
+      height:200px;
+      width:450px;
+      overflow-y:scroll;
+      border-width:1px;
+      border-style: solid;
+      display: grid;
+      grid-template-columns: 1fr 2fr;
+    '>
+      {}
+    </div></div>", rows);
+  rows = format!("<div style='display: flex; justify-content: center;'>{}</div>", rows);
+  let errors = result.points.iter().filter(|point| point.error.is_some()).count();
+  let plot = svg::plot(&[
+    svg::Series {label: "f(x)".to_string(), points: result.points.iter().filter_map(|point| point.f.map(|f| [point.x, f])).collect(), style: svg::Style::Dots},
+  ], "x", "f");
+  content::Html(format!(
+    "{}<br><br><b>result</b>: Values of the function f(x) = {} at {} points from x = {} to x = {}, spaced {}.{}<br>{}<br>{}",
+    instructions,
+    str::replace(&expression, "X", "x"),
+    result.n,
+    result.a,
+    result.b,
+    if result.spacing == "log" {"logarithmically"} else {"linearly"},
+    if errors == 0 {"".to_string()} else {format!("  The function could not be evaluated at {} of them.", errors)},
+    rows,
+    plot,
+  ))
+}
+
 fn main() {
   rocket::ignite().mount("/", routes![index,
   differentiation_page, differentiation_json, differentiation_html,integration_page, integration_json, integration_html,
   root_finding_page, root_finding_json, root_finding_html,
   max_finding_page, max_finding_json, max_finding_html,
+  min_finding_page, min_finding_json, min_finding_html,
   ode_page, ode_json, ode_html,
-  ode2_page, ode2_json, ode2_html]).launch();
+  ode2_page, ode2_json, ode2_html,
+  nonlinear_systems_page, nonlinear_systems_json, nonlinear_systems_html,
+  global_max_page, global_max_json, global_max_html,
+  optimization_page, optimization_json, optimization_html,
+  constrained_optimization_page, constrained_optimization_json, constrained_optimization_html,
+  curve_fitting_page, curve_fitting_json,
+  analysis_page, analysis_json, analysis_html,
+  odes_page, odes_json, odes_html,
+  bvp_page, bvp_json, bvp_html,
+  sturm_liouville_page, sturm_liouville_json, sturm_liouville_html,
+  oden_page, oden_json, oden_html,
+  dde_page, dde_json, dde_html,
+  sde_page, sde_json, sde_html,
+  pde_page, pde_json, pde_html,
+  direction_field_page, direction_field_json, direction_field_html,
+  table_page, table_json, table_csv, table_html]).launch();
 }
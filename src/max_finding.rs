@@ -3,22 +3,26 @@ use serde::{Serialize, Deserialize};
 
 use crate::helper;
 
-fn instructions() -> helper::LongPage {
+fn instructions(mode: &str) -> helper::LongPage {
+	let (title, n_link) = if mode == "min" {("MIN-FINDING", 6)} else {("MAX-FINDING", 5)};
 	helper::LongPage {
-		title: "MAX-FINDING".to_string(),
-		links:  calculus::links(5),
-		instructions: "In the url bar after <tt>'https://basic-calculus.herokuapp.com/max-finding</tt> type the following:<p align=center>&sol;&lt;point at which to start search for a maximum&gt;&sol;&lt;function of <i>x</I>&gt;</tt></p>Note that this will not necessarily find the local maximum which is <i>closest</i> to the input point.".to_string(),
+		title: title.to_string(),
+		links:  calculus::links(n_link),
+		instructions: format!("In the url bar after <tt>'https://basic-calculus.herokuapp.com/{}-finding</tt> type the following:<p align=center>&sol;&lt;point at which to start search for a {}imum&gt;&sol;&lt;function of <i>x</I>&gt;</tt></p>Note that this will not necessarily find the local {}imum which is <i>closest</i> to the input point.  A local m<i>in</i>imum may be found either by using the url <tt>/min-finding</tt> or by appending <tt>?mode=min</tt> to the url for max-finding (and vice versa, with <tt>?mode=max</tt>).", mode, mode, mode),
 		note: format!("{}{}", helper::NOTE1, helper::NOTE2).to_string(),
-		example: "To find a local maximum of the function sin <i>x</i> + <i>x</i>/2 while starting the search at <i>x</i> = 1, type <tt>/1/sin(x)+xd2</tt> after the current url address.  The coordinates for this result should be <tt>(2.094..., 1.913...)</tt>.  If you want to find a local m<i>in</I>imum, simply multiply your function by -1.".to_string(),
-		algorithm: "simple bisection (and quadratic interpolation?)".to_string(),
-		json: "Type '/json' in the url bar immediately after 'max-finding' if you would like the result in this format rather than html.  A successful response will contain six properties. 'xi' is the location where the search starts, 'x' is where the search ends, 'f' is the function value there, 'bracket_steps' is the number of steps required to find numbers on either side of (ie, to 'bracket') the maximum, and 'max_steps' is the subsequent number of steps required for the algorithm to find this maximum to within the absolute accuracy specified in the last property: 'epsilon'. An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+		example: "To find a local maximum of the function sin <i>x</i> + <i>x</i>/2 while starting the search at <i>x</i> = 1, type <tt>/1/sin(x)+xd2</tt> after /max-finding in the url above.  The coordinates for this result should be <tt>(2.094..., 1.913...)</tt>.  To find a local minimum of the same function while starting the search at <i>x</i> = 4, type <tt>/4/sin(x)+xd2</tt> after /min-finding.  The coordinates for this result should be <tt>(4.188..., 1.228...)</tt>.".to_string(),
+		algorithm: "After the extremum is bracketed by steps which grow geometrically, Brent's method (which combines parabolic interpolation with golden-section search) locates it to within a relative accuracy equal to the square root of the machine precision.".to_string(),
+		json: format!("Type '/json' in the url bar immediately after '{}-finding' if you would like the result in this format rather than html.  A successful response will contain seven properties. 'mode' is either 'max' or 'min', 'xi' is the location where the search starts, 'x' is where the search ends, 'f' is the function value there, 'bracket_steps' is the number of steps required to find numbers on either side of (ie, to 'bracket') the extremum, and 'max_steps' is the subsequent number of steps required for the algorithm to find this extremum to within the relative accuracy specified in the last property: 'epsilon'. An unsuccessful response will have one property: 'message' (a string reporting the error).", mode),
 	}
 }
 
-pub fn page() -> String {helper::format(instructions())}
+pub fn page() -> String {helper::format(instructions("max"))}
+
+pub fn min_page() -> String {helper::format(instructions("min"))}
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Results {
+	pub mode: String,
 	pub xi: f64,
 	pub x: f64,
 	pub f: f64,
@@ -27,10 +31,104 @@ pub struct Results {
 	pub epsilon: f64,
 }
 
-pub fn raw (xi_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
-	let max_steps_max = 50;
-	let epsilon = (10_f64).powf(-5.);
+// Brent's method for locating the minimum of g which has been bracketed by a < x < b, where g(x) = gx
+// is smaller than g's values at a and b.  Returned are the location and value of the minimum, and the number of steps taken.
+pub fn brent<F: Fn(f64) -> Result<f64, String>>(g: &F, a: f64, b: f64, x: f64, gx: f64) -> Result<(f64, f64, i32), String> {
+	let steps_max = 100;
+	let tol = f64::EPSILON.sqrt();
+	// This prevents an infinite loop when the minimum is at x = 0.
+	let zeps = f64::EPSILON * 1e-3;
+	// golden-section ratio
+	let cgold = (3. - (5_f64).sqrt()) / 2.;
+	let (mut a, mut b) = if a < b {(a, b)} else {(b, a)};
+	// x has the smallest function value so far, w the next smallest, and v the previous value of w.
+	let (mut x, mut w, mut v) = (x, x, x);
+	let (mut gx, mut gw, mut gv) = (gx, gx, gx);
+	// d is the latest step, and e is the one before it.
+	let mut d: f64 = 0.;
+	let mut e: f64 = 0.;
+	for steps in 0..steps_max {
+		let xm = (a + b) / 2.;
+		let tol1 = tol * x.abs() + zeps;
+		let tol2 = 2. * tol1;
+		if (x - xm).abs() <= tol2 - (b - a) / 2. {
+			return Ok((x, gx, steps));
+		}
+		let mut golden = true;
+		if e.abs() > tol1 {
+			// parabola thru x, w, and v
+			let r = (x - w) * (gx - gv);
+			let mut q = (x - v) * (gx - gw);
+			let mut p = (x - v) * q - (x - w) * r;
+			q = 2. * (q - r);
+			if q > 0. {
+				p = -p;
+			}
+			q = q.abs();
+			// Use the parabolic step only if it is in the bracket and is less than half of the step before last.
+			if p.abs() < (q * e / 2.).abs() && p > q * (a - x) && p < q * (b - x) {
+				e = d;
+				d = p / q;
+				let u = x + d;
+				if u - a < tol2 || b - u < tol2 {
+					d = tol1.copysign(xm - x);
+				}
+				golden = false;
+			}
+		}
+		if golden {
+			e = if x >= xm {a - x} else {b - x};
+			d = cgold * e;
+		}
+		let u = if d.abs() >= tol1 {x + d} else {x + tol1.copysign(d)};
+		let gu = match g(u) {
+			Ok(gu) => gu,
+			Err(message) => return Err(message),
+		};
+		if gu <= gx {
+			if u >= x {
+				a = x;
+			} else {
+				b = x;
+			}
+			v = w;
+			gv = gw;
+			w = x;
+			gw = gx;
+			x = u;
+			gx = gu;
+		} else {
+			if u < x {
+				a = u;
+			} else {
+				b = u;
+			}
+			if gu <= gw || w == x {
+				v = w;
+				gv = gw;
+				w = u;
+				gw = gu;
+			} else if gu <= gv || v == x || v == w {
+				v = u;
+				gv = gu;
+			}
+		}
+	}
+	Err(format!("Unable to locate a bracketed extremum within {} steps.", steps_max))
+}
+
+pub fn raw (xi_str: &RawStr, input_str: &RawStr, mode: &str) -> Result<Results, String> {
 	let bracket_steps_max = 30;
+	// Finding a minimum of f is equivalent to finding a maximum of -f.
+	let sign = match mode {
+		"max" => 1.,
+		"min" => -1.,
+		_ => return Err(format!("Error: the mode must be either 'max' or 'min', not '{}'.", mode)),
+	};
+	let h = |x: f64| match helper::function1(input_str.to_string(), x) {
+		Ok(f) => Ok(sign * f),
+		Err(message) => Err(message),
+	};
 	let xi = match helper::parse_expression(xi_str.to_string()) {
 	  	Ok(xi) => xi,
 	  	Err(message) => return Err(message),
@@ -38,18 +136,18 @@ pub fn raw (xi_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
 	let mut x1 = xi;
 	// arbitrary
 	let mut step = 0.1;
-	// First, bracket the root.
+	// First, bracket the extremum.
 	let mut x0 = x1 - step / 2.;
 	let mut x2 = x1 + step / 2.;
-	let mut f0 = match helper::function1(input_str.to_string(), x0) {
+	let mut f0 = match h(x0) {
 		Ok(f0) => f0,
 		Err(message) => return Err(message),
 	};
-	let mut f1 = match helper::function1(input_str.to_string(), x1) {
+	let mut f1 = match h(x1) {
 		Ok(f1) => f1,
 		Err(message) => return Err(message),
 	};
-	let mut f2 = match helper::function1(input_str.to_string(), x2) {
+	let mut f2 = match h(x2) {
 		Ok(f2) => f2,
 		Err(message) => return Err(message),
 	};
@@ -63,7 +161,7 @@ pub fn raw (xi_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
 			x1 = x2;
 			f1 = f2;
 			x2 += step;
-			f2 = match helper::function1(input_str.to_string(), x2) {
+			f2 = match h(x2) {
 				Ok(f2) => f2,
 				Err(message) => return Err(message),
 			};
@@ -73,69 +171,28 @@ pub fn raw (xi_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
 			x1 = x0;
 			f1 = f0;
 			x0 -= step;
-			f0 = match helper::function1(input_str.to_string(), x0) {
+			f0 = match h(x0) {
 				Ok(f0) => f0,
 				Err(message) => return Err(message),
 			};
 		}
 		bracket_steps += 1;
 		if bracket_steps > bracket_steps_max {
-			return Err(format!("Unable to bracket a max after {} steps.", bracket_steps_max));
+			return Err(format!("Unable to bracket a {} after {} steps.", mode, bracket_steps_max));
 		}
 	}
-	let mut max_steps = 0;
-	// Following two vars will be sequential estimates using parabolic interpolation.
-	let mut x_old = -f64::INFINITY;
-	let mut x_new = f64::INFINITY;
-	while (x_old - x_new).abs() > epsilon {
-		if max_steps > max_steps_max {
-			return Err(format!("Unable to locate a bracketed max within {} steps.", max_steps_max));
-		}
-		// Bisect the segment for which the outer function value is smallest.
-		let x = (x1 + if f0 > f2 { x2 } else { x0 }) / 2.;
-		let f = match helper::function1(input_str.to_string(), x) {
-			Ok(f) => f,
-			Err(message) => return Err(message),
-		};
-		if x < x1 {
-			if f < f1 {
-				x0 = x;
-				f0 = f;
-			} else {
-				x2 = x1;
-				f2 = f1;
-				x1 = x;
-				f1 = f;
-			}
-		} else {
-			if f < f1 {
-				x2 = x;
-				f2 = f;
-			} else {
-				x0 = x1;
-				f0 = f1;
-				x1 = x;
-				f1 = f;
-			}
-		}
-		x_old = x_new;
-		// parabolic interpolation
-		let num = (x1 - x0) * (x1 - x0) * (f1 - f2) - (x1 - x2) * (x1 - x2) * (f1 - f0);
-		let den = (x1 - x0) * (f1 - f2) - (x1 - x2) * (f1 - f0);
-		x_new = x1 - num / den / 2.;
-		max_steps += 1;
-	}
-	let f = match helper::function1(input_str.to_string(), x_new) {
-		Ok(f) => f,
+	// Second, locate the extremum by using Brent's method to find the minimum of -h.
+	let (x, g, max_steps) = match brent(&|x| h(x).map(|f| -f), x0, x2, x1, -f1) {
+		Ok(result) => result,
 		Err(message) => return Err(message),
 	};
-
 	Ok(Results {
+		mode: mode.to_string(),
 		xi,
-		x: x_new,
-		f,
+		x,
+		f: -sign * g,
 		bracket_steps,
 		max_steps,
-		epsilon,
+		epsilon: f64::EPSILON.sqrt(),
 	})
 }
@@ -0,0 +1,176 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::linear_algebra;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "SYSTEMS OF NONLINEAR EQUATIONS".to_string(),
+		links:  calculus::links(9),
+		instructions: "This page solves <i>n</i> simultaneous equations in <i>n</i> unknowns, each equation being either of the form (function of the unknowns) = 0 or of the form (function of the unknowns) = (function of the unknowns).  In the url bar after <tt>'https://basic-calculus.herokuapp.com/nonlinear-systems</tt> type the following:<p align=center>&sol;&lt;comma-separated names of the unknowns&gt;&sol;&lt;comma-separated values at which to start the search&gt;&sol;&lt;comma-separated functions of (or equations for) the unknowns&gt;</tt></p>Each name must start with a letter and may contain letters, digits, and underscores.  Note that this will not necessarily find the solution which is <i>closest</i> to the starting point.".to_string(),
+		note: format!("{}{}", helper::NOTE1, helper::NOTE2).to_string(),
+		example: "To find a solution of the equations <i>x</i><sup>2</sup> + <i>y</i><sup>2</sup> - 4 = 0 and <i>xy</i> - 1 = 0 while starting the search at (<i>x</i>, <i>y</i>) = (2, 1), type <tt>/x,y/2,1/x**2+y**2-4,x*y-1</tt> after the current url address.  The result for this should be <tt>(1.931..., 0.517...)</tt>.".to_string(),
+		algorithm: "Newton's method with a line search, using Broyden's update of the Jacobian matrix after it has been calculated once by finite differences.  The Jacobian is recalculated by finite differences whenever Broyden's update fails to reduce the residual.".to_string(),
+		json: "Type '/json' in the url bar immediately after 'nonlinear-systems' if you would like the result in this format rather than html.  A successful response will contain seven properties. 'vars' is the list of names of the unknowns, 'xi' is the point where the search starts, 'x' is the solution that is eventually found, 'residual' is the root-mean-square of the functions' values there, 'steps' is the number of iterations required, 'jacobians' is the number of times that the Jacobian needed to be calculated by finite differences, and 'epsilon' is the accuracy to which the residual must vanish. An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub vars: Vec<String>,
+	pub xi: Vec<f64>,
+	pub x: Vec<f64>,
+	pub residual: f64,
+	pub steps: i32,
+	pub jacobians: i32,
+	pub epsilon: f64,
+}
+
+fn functions(expressions: &[String], vars: &[String], x: &[f64]) -> Result<Vec<f64>, String> {
+	let mut fs = vec![];
+	for expression in expressions {
+		fs.push(match helper::function_n(expression, vars, x) {
+			Ok(f) => f,
+			Err(message) => return Err(message),
+		});
+	}
+	Ok(fs)
+}
+
+// forward-difference approximation of the Jacobian matrix
+fn jacobian(expressions: &[String], vars: &[String], x: &[f64], fs: &[f64]) -> Result<Vec<Vec<f64>>, String> {
+	let mut jac = vec![vec![0.; x.len()]; fs.len()];
+	for j in 0..x.len() {
+		let dx = 1e-7 * x[j].abs().max(1.);
+		let mut x_new = x.to_vec();
+		x_new[j] += dx;
+		let fs_new = match functions(expressions, vars, &x_new) {
+			Ok(fs_new) => fs_new,
+			Err(message) => return Err(message),
+		};
+		for i in 0..fs.len() {
+			jac[i][j] = (fs_new[i] - fs[i]) / dx;
+		}
+	}
+	Ok(jac)
+}
+
+pub fn raw(vars_str: &RawStr, xi_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
+	let epsilon = (10_f64).powf(-12.);
+	let steps_max = 100;
+	let vars = match helper::parse_names(vars_str) {
+		Ok(vars) => vars,
+		Err(message) => return Err(message),
+	};
+	let mut xi = vec![];
+	for x_str in helper::parse_list(xi_str) {
+		xi.push(match helper::parse_expression(x_str) {
+			Ok(x) => x,
+			Err(message) => return Err(message),
+		});
+	}
+	let mut expressions = vec![];
+	for expression in helper::parse_list(input_str) {
+		expressions.push(match helper::equation(&expression, &vars) {
+			Ok(expression) => expression,
+			Err(message) => return Err(message),
+		});
+	}
+	if xi.len() != vars.len() || expressions.len() != vars.len() {
+		return Err(format!("Error: there are {} unknowns, {} starting values, and {} equations, but these numbers must be equal.", vars.len(), xi.len(), expressions.len()));
+	}
+	let n = vars.len() as f64;
+	let mut x = xi.clone();
+	let mut fs = match functions(&expressions, &vars, &x) {
+		Ok(fs) => fs,
+		Err(message) => return Err(message),
+	};
+	let mut residual = linear_algebra::norm(&fs) / n.sqrt();
+	let mut jac = match jacobian(&expressions, &vars, &x, &fs) {
+		Ok(jac) => jac,
+		Err(message) => return Err(message),
+	};
+	let mut jacobians = 1;
+	// whether jac was calculated by finite differences at the current point (rather than by Broyden's update)
+	let mut fresh = true;
+	let mut steps = 0;
+	while residual > epsilon {
+		if steps >= steps_max {
+			return Err(format!("Unable to find a solution within {} steps.", steps_max));
+		}
+		let minus_fs: Vec<f64> = fs.iter().map(|f| -f).collect();
+		let dx = match linear_algebra::solve(&jac, &minus_fs) {
+			Ok(dx) => dx,
+			Err(message) => {
+				if fresh {
+					return Err(format!("{}  The Jacobian matrix is singular at {:?}.", message, x));
+				}
+				// A singular Broyden update will be replaced by finite differences, below.
+				vec![]
+			},
+		};
+		// Shorten the Newton step until the residual decreases.
+		let mut lambda = 1.;
+		let mut improved = None;
+		if !dx.is_empty() {
+			while lambda > 1e-4 {
+				let x_new: Vec<f64> = x.iter().zip(&dx).map(|(xj, dxj)| xj + lambda * dxj).collect();
+				// Allow the line search to step around points where a function cannot be evaluated.
+				if let Ok(fs_new) = functions(&expressions, &vars, &x_new) {
+					let residual_new = linear_algebra::norm(&fs_new) / n.sqrt();
+					if residual_new < (1. - 1e-4 * lambda) * residual {
+						improved = Some((x_new, fs_new, residual_new));
+						break;
+					}
+				}
+				lambda /= 2.;
+			}
+		}
+		match improved {
+			Some((x_new, fs_new, residual_new)) => {
+				// Broyden's rank-one update of the Jacobian
+				let delta_x: Vec<f64> = x_new.iter().zip(&x).map(|(a, b)| a - b).collect();
+				let delta_x2 = linear_algebra::dot(&delta_x, &delta_x);
+				for i in 0..fs.len() {
+					let error = fs_new[i] - fs[i] - linear_algebra::dot(&jac[i], &delta_x);
+					for j in 0..x.len() {
+						jac[i][j] += error * delta_x[j] / delta_x2;
+					}
+				}
+				fresh = false;
+				let size = linear_algebra::norm(&delta_x);
+				x = x_new;
+				fs = fs_new;
+				residual = residual_new;
+				steps += 1;
+				// The residual may not be able to reach epsilon, if the functions are large.
+				if size < epsilon * (1. + linear_algebra::norm(&x)) {
+					break;
+				}
+			},
+			None => {
+				if fresh {
+					return Err(format!("Unable to reduce the residual below {} after {} steps, at the point {:?}.", residual, steps, x));
+				}
+				jac = match jacobian(&expressions, &vars, &x, &fs) {
+					Ok(jac) => jac,
+					Err(message) => return Err(message),
+				};
+				jacobians += 1;
+				fresh = true;
+			},
+		}
+	}
+	Ok(Results {
+		vars,
+		xi,
+		x,
+		residual,
+		steps,
+		jacobians,
+		epsilon,
+	})
+}
@@ -2,16 +2,19 @@ use rocket::http::RawStr;
 use serde::{Serialize, Deserialize};
 
 use crate::helper;
+use crate::integrators;
+
+const EXACT: &str = "If the exact solution is known, appending for instance <tt>&exact=exp(-t)</tt> (a function of <i>t</i>) to the url will also report the global error (the difference between the numerical and exact solutions) at each time.";
 
 fn instructions() -> helper::LongPage {
 	helper::LongPage {
 		title: "1ST-ORDER DIFFERENTIAL EQUATIONS".to_string(),
-		links:  calculus::links(6),
-		instructions: "This page solves a differential equation of the form <i>dx/dt</I> = function of <I>x</I> and <I>t</I>, with a specified 'initial condition', ie a value of <I>x</I> when the 'time' <i>t</i> = 0.  In the url bar after <tt>'https://basic-calculus.herokuapp.com/ode</tt> type the following:<p align=center>&sol;&lt;initial value of <i>x</I>&gt;&sol;&lt;final value of <i>t</I>&gt;&sol;&lt;number of time-steps&gt;&sol;&lt;function of <i>x</I> and <i>t</I>&gt;</tt></p>".to_string(),
+		links:  calculus::links(7),
+		instructions: format!("This page solves a differential equation of the form <i>dx/dt</I> = function of <I>x</I> and <I>t</I>, with a specified 'initial condition', ie a value of <I>x</I> when the 'time' <i>t</i> = 0 (by default).  In the url bar after <tt>'https://basic-calculus.herokuapp.com/ode</tt> type the following:<p align=center>&sol;&lt;initial value of <i>x</I>&gt;&sol;&lt;final value of <i>t</I>&gt;&sol;&lt;number of time-steps&gt;&sol;&lt;function of <i>x</I> and <i>t</I>&gt;</tt></p>{}  {}  {}  {}", integrators::INITIAL_TIME, integrators::METHODS, EXACT, integrators::EVENTS),
 		note: format!("{}{}", helper::NOTE1, helper::NOTE2).to_string(),
-		example: "To solve the equation dx/dt = 2x - t - 2 from t = 0 to t = 2 using 10 time steps and the initial condition that x(0) = 1, type <tt>/1/2/10/2x-t-2</tt> after /ode in the url above.  The final result should be that x(2) = -11.39..".to_string(),
-		algorithm: "4th-order Runge-Kutta method".to_string(),
-		json: "Type '/json' in the url bar immediately after 'ode' if you would like the result in this format rather than html.  All of the data are returned.".to_string(),
+		example: "To solve the equation dx/dt = 2x - t - 2 from t = 0 to t = 2 using 10 time steps and the initial condition that x(0) = 1, type <tt>/1/2/10/2x-t-2</tt> after /ode in the url above.  The final result should be that x(2) = -11.39..  Appending <tt>?method=rk45</tt> should instead give the more accurate result that x(2) = -11.3995..., after 21 accepted and 2 rejected steps.  For the stiff equation dx/dt = -1000(x - cos t), typing <tt>/0/1/10/-1000(x-cos(t))</tt> yields a solution which diverges, whereas appending <tt>?method=bdf3</tt> yields the accurate result that x(1) = 0.54114....  To compare methods, type <tt>/1/2/10/-x?method=euler&exact=exp(-t)</tt>, for which the final error should be 0.0279..., and then replace <tt>euler</tt> by <tt>heun</tt>, <tt>rk3</tt>, or <tt>rk4</tt>, and/or double the number of time-steps.".to_string(),
+		algorithm: integrators::ALGORITHM.to_string(),
+		json: "Type '/json' in the url bar immediately after 'ode' if you would like the result in this format rather than html.  All of the data are returned (including the initial time 'ti'), including the method, its numbers of accepted and rejected steps, (for an adaptive method) its tolerances, and (for the automatic method) the time at which it switches to the stiff solver.  'ts' is the array of output times, and 'events' is an array of the events which were found, each of which has the properties 'event' (the expression), 't' (the time), and 'y' (the variables' values at that time).  If an exact solution is specified, 'errors' is the array of global errors at the output times (otherwise it is null).".to_string(),
 	}
 }
 
@@ -19,13 +22,46 @@ pub fn page() -> String {helper::format(instructions())}
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Results {
+	pub ti: f64,
 	pub xi: f64,
 	pub tf: f64,
 	pub nt: i32,
+	pub ts: Vec<f64>,
 	pub xs: Vec<f64>,
+	pub method: String,
+	pub rtol: Option<f64>,
+	pub atol: Option<f64>,
+	pub accepted: i32,
+	pub rejected: i32,
+	pub switched: Option<f64>,
+	pub events: Vec<integrators::Event>,
+	pub errors: Option<Vec<f64>>,
 }
 
-pub fn raw (xi_str: &RawStr, tf_str: &RawStr, nt_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
+pub fn raw (
+	xi_str: &RawStr,
+	tf_str: &RawStr,
+	nt_str: &RawStr,
+	input_str: &RawStr,
+	ti_str: Option<&str>,
+	method: Option<&str>,
+	rtol_str: Option<&str>,
+	atol_str: Option<&str>,
+	events_str: Option<&str>,
+	terminal_str: Option<&str>,
+	exact_str: Option<&str>,
+) -> Result<Results, String> {
+	let settings = match integrators::settings(method, rtol_str, atol_str) {
+		Ok(settings) => settings,
+		Err(message) => return Err(message),
+	};
+	if settings.symplectic() {
+		return Err(format!("Error: the {} method applies only to 2nd-order equations.", settings.method));
+	}
+	let ti = match integrators::initial_time(ti_str) {
+		Ok(ti) => ti,
+		Err(message) => return Err(message),
+	};
 	let xi = match helper::parse_expression(xi_str.to_string()) {
 	  	Ok(x0) => x0,
 	  	Err(message) => return Err(message),
@@ -45,28 +81,68 @@ pub fn raw (xi_str: &RawStr, tf_str: &RawStr, nt_str: &RawStr, input_str: &RawSt
 		},
 		Err(message) => return Err(message),
   	};
-	let mut xs = vec![xi];
-	let dt = tf / (nt as f64);
-	for i in 0..nt {
-		let t = (i as f64) * tf / (nt as f64);
-		let x = xs[i as usize];
-		let v1 = match helper::function2(input_str.to_string(), x, t) {
-			Ok(v) => v,
-			Err(message) => return Err(message),
-		};
-		let v2 = match helper::function2(input_str.to_string(), x + v1 * dt / 2., t + dt / 2.) {
-			Ok(v) => v,
-			Err(message) => return Err(message),
-		};
-		let v3 = match helper::function2(input_str.to_string(), x + v2 * dt / 2., t + dt / 2.) {
-			Ok(v) => v,
-			Err(message) => return Err(message),
-		};
-		let v4 = match helper::function2(input_str.to_string(), x + v3 * dt, t + dt) {
-			Ok(v) => v,
-			Err(message) => return Err(message),
-		};
-		xs.push(x + ((v1 + v4) + 2. * (v2 + v3)) * dt / 6.);
+	let f = |t: f64, y: &[f64]| match helper::function2(input_str.to_string(), y[0], t) {
+		Ok(v) => Ok(vec![v]),
+		Err(message) => Err(message),
+	};
+	let names: Vec<String> = ["x", "t"].iter().map(|name| name.to_string()).collect();
+	let events = match integrators::events(events_str, terminal_str, &names) {
+		Ok(events) => events,
+		Err(message) => return Err(message),
+	};
+	let solution = match integrators::solve(&f, ti, &[xi], tf, nt, &settings, &events) {
+		Ok(solution) => solution,
+		Err(message) => return Err(message),
+	};
+	// global error at each output time, ie the difference between the numerical and exact solutions
+	let errors = match exact_str {
+		Some(exact_str) => {
+			let mut errors = vec![];
+			for (t, y) in solution.ts.iter().zip(&solution.ys) {
+				errors.push(match helper::function_n(exact_str, &["t".to_string()], &[*t]) {
+					Ok(exact) => y[0] - exact,
+					Err(message) => return Err(message),
+				});
+			}
+			Some(errors)
+		},
+		None => None,
+	};
+	let adaptive = settings.adaptive();
+	return Ok(Results {
+		ti,
+		xi,
+		tf,
+		nt,
+		ts: solution.ts,
+		xs: solution.ys.iter().map(|y| y[0]).collect(),
+		method: settings.method,
+		rtol: if adaptive {Some(settings.rtol)} else {None},
+		atol: if adaptive {Some(settings.atol)} else {None},
+		accepted: solution.accepted,
+		rejected: solution.rejected,
+		switched: solution.switched,
+		events: solution.events,
+		errors,
+	});
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// dx/dt = x with x(1) = 1, solved forward and backward from the nonzero initial time
+	#[test]
+	fn nonzero_initial_time() {
+		for (tf, method) in [("2", "rk4"), ("0", "rk4"), ("2", "rk45"), ("0", "rk45")] {
+			let results = raw(RawStr::from_str("1"), RawStr::from_str(tf), RawStr::from_str("10"), RawStr::from_str("x"), Some("1"), Some(method), None, None, None, None, Some("exp(t-1)")).unwrap();
+			assert_eq!(results.ti, 1.);
+			assert_eq!(results.ts[0], 1.);
+			assert_eq!(results.xs[0], 1.);
+			assert_eq!(*results.ts.last().unwrap(), results.tf);
+			for error in results.errors.unwrap() {
+				assert!(error.abs() < 1e-5, "{} to t = {}: error = {}", method, tf, error);
+			}
+		}
 	}
-	return Ok(Results {xi, tf, nt, xs});
 }
@@ -2,16 +2,19 @@ use rocket::http::RawStr;
 use serde::{Serialize, Deserialize};
 
 use crate::helper;
+use crate::integrators;
+
+const CHAOS: &str = "For an oscillator which is driven periodically, appending for instance <tt>&period=2PI</tt> to the url also reports the 'Poincaré section', ie the values of <i>x</i> and <i>v</i> after each whole number of periods of the drive.  A periodic motion yields a few points which repeat, whereas a chaotic one yields points which never repeat.  Appending <tt>&lyapunov=true</tt> also reports an estimate of the largest 'Lyapunov exponent', ie the rate at which the distance between two solutions which start very close to each other grows exponentially.  This is positive for chaotic motion, and it is more accurate for a longer time.";
 
 fn instructions() -> helper::LongPage {
 	helper::LongPage {
 		title: "2ND-ORDER DIFFERENTIAL EQUATIONS".to_string(),
-		links:  calculus::links(7),
-		instructions: "This page solves a differential equation of the form <i>d</I><sup>2</sup><i>x/dt</i><sup>2</sup> = function of <I>x</I>, of <I>dx/dt</I> (= '<i>v</I>'), and of 'time' <I>t</I>, with a specified 'initial condition', ie values of <I>x</I> and of <i>v</I> when the 'time' <i>t</i> = 0. In the url bar after <tt>'https://basic-calculus.herokuapp.com/ode2</tt> type the following:<p align=center>&sol;&lt;initial value of <i>x</I>&gt;&sol;&lt;initial value of <i>v</I> v&gt;&sol;&lt;final value of <i>t</I>&gt;&sol;&lt;number of time-steps&gt;&sol;&lt;function of <i>x</I>, <i>v</I>, and <i>t</I>&gt;</tt></p>".to_string(),
+		links:  calculus::links(8),
+		instructions: format!("This page solves a differential equation of the form <i>d</I><sup>2</sup><i>x/dt</i><sup>2</sup> = function of <I>x</I>, of <I>dx/dt</I> (= '<i>v</I>'), and of 'time' <I>t</I>, with a specified 'initial condition', ie values of <I>x</I> and of <i>v</I> when the 'time' <i>t</i> = 0 (by default). In the url bar after <tt>'https://basic-calculus.herokuapp.com/ode2</tt> type the following:<p align=center>&sol;&lt;initial value of <i>x</I>&gt;&sol;&lt;initial value of <i>v</I> v&gt;&sol;&lt;final value of <i>t</I>&gt;&sol;&lt;number of time-steps&gt;&sol;&lt;function of <i>x</I>, <i>v</I>, and <i>t</I>&gt;</tt></p>{}  {}  {}  {}  {}", integrators::INITIAL_TIME, integrators::METHODS, integrators::SYMPLECTIC, integrators::EVENTS, CHAOS),
 		note: format!("{}{}", helper::NOTE1, helper::NOTE2).to_string(),
-		example: "To solve the equation d<sup>2</sup>/dt<sup>2</sup> = -2x - v + 3t with the initial conditions that x(0) = 0 and dx/dt = v(0) = 1 over the range 0 < t < 4 using 10 time-steps, type <tt>/0/1/4/10/-2x-v+3t</tt> after /ode2 in the url above.  In this case the final values for x and dx/dt should be 5.31... and 1.57..., respectively.".to_string(),
-		algorithm: "4th-order Runge-Kutta method".to_string(),
-		json: "Type '/json' in the url bar immediately after 'ode2' if you would like the result in this format rather than html.  All data are returned.".to_string(),
+		example: "To solve the equation d<sup>2</sup>/dt<sup>2</sup> = -2x - v + 3t with the initial conditions that x(0) = 0 and dx/dt = v(0) = 1 over the range 0 < t < 4 using 10 time-steps, type <tt>/0/1/4/10/-2x-v+3t</tt> after /ode2 in the url above.  In this case the final values for x and dx/dt should be 5.31... and 1.57..., respectively, whereas appending <tt>?method=rk45</tt> should give the more accurate values 5.3162... and 1.5699....  For a ball thrown upward at 10 m/s, typing <tt>/0/10/5/50/-9.8?events=x,v&terminal=true</tt> stops the integration at the apex, when v = 0 at t = 1.0204..., whereas omitting <tt>&terminal=true</tt> also finds that the ball lands (x = 0) at t = 2.0408....  For an oscillator integrated for a long time, typing <tt>/1/0/1000/2000/-x?energy=v**2/2+x**2/2</tt> shows that the default method loses about a third of the energy (whose largest deviation from its initial value 0.5 is 0.171...), whereas appending <tt>&method=verlet</tt> keeps the energy within 0.0312... of 0.5.  For the chaotic Duffing oscillator d<sup>2</sup>x/dt<sup>2</sup> = -0.3v + x - x<sup>3</sup> + 0.5cos(1.2t) over 200 periods (each of which is 2&pi;/1.2 = 5&pi;/3), typing <tt>/1/0/1000PId3/2000/-0.3v+x-x**3+0.5cos(1.2t)?period=5PId3&lyapunov=true</tt> gives a Poincaré section which is scattered over a fractal set and a Lyapunov exponent of 0.12 or so, whereas replacing 0.5cos by 0.2cos gives a section which soon becomes a single repeated point and an exponent of -0.15 or so.".to_string(),
+		algorithm: format!("{}  The points of the Poincaré section are calculated with the method's interpolant.  The Lyapunov exponent is estimated by integrating (alongside the solution) a second solution which starts at a distance of 10<sup>-8</sup> from it and which, at each of the output times, is moved back to this distance from the solution in the direction in which it has moved away.  The exponent is the average rate of growth of the logarithm of the distance (Benettin's method).", integrators::ALGORITHM),
+		json: "Type '/json' in the url bar immediately after 'ode2' if you would like the result in this format rather than html.  All data are returned (including the initial time 'ti'), including the method, its numbers of accepted and rejected steps, (for an adaptive method) its tolerances, and (for the automatic method) the time at which it switches to the stiff solver.  'ts' is the array of output times, and 'events' is an array of the events which were found, each of which has the properties 'event' (the expression), 't' (the time), and 'y' (the variables' values at that time).  If an energy is specified, 'energies' is the array of its values at the output times and 'drift' is its largest deviation from its initial value (otherwise both are null).  If a period is specified, 'section' is the array of the points of the Poincaré section, each of which is the array of the values of <i>t</i>, <i>x</i>, and <i>v</i> (otherwise it is null), and if requested 'lyapunov' is the estimate of the largest Lyapunov exponent (otherwise it is null).".to_string(),
 	}
 }
 
@@ -19,15 +22,51 @@ pub fn page() -> String {helper::format(instructions())}
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Results {
+	pub ti: f64,
 	pub xi: f64,
 	pub vi: f64,
 	pub tf: f64,
 	pub nt: i32,
+	pub ts: Vec<f64>,
 	pub xs: Vec<f64>,
 	pub vs: Vec<f64>,
+	pub method: String,
+	pub rtol: Option<f64>,
+	pub atol: Option<f64>,
+	pub accepted: i32,
+	pub rejected: i32,
+	pub switched: Option<f64>,
+	pub events: Vec<integrators::Event>,
+	pub energies: Option<Vec<f64>>,
+	pub drift: Option<f64>,
+	pub section: Option<Vec<[f64; 3]>>,
+	pub lyapunov: Option<f64>,
 }
 
-pub fn raw (xi_str: &RawStr, vi_str: &RawStr, tf_str: &RawStr, nt_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
+pub fn raw (
+	xi_str: &RawStr,
+	vi_str: &RawStr,
+	tf_str: &RawStr,
+	nt_str: &RawStr,
+	input_str: &RawStr,
+	ti_str: Option<&str>,
+	method: Option<&str>,
+	rtol_str: Option<&str>,
+	atol_str: Option<&str>,
+	events_str: Option<&str>,
+	terminal_str: Option<&str>,
+	energy_str: Option<&str>,
+	period_str: Option<&str>,
+	lyapunov_str: Option<&str>,
+) -> Result<Results, String> {
+	let settings = match integrators::settings(method, rtol_str, atol_str) {
+		Ok(settings) => settings,
+		Err(message) => return Err(message),
+	};
+	let ti = match integrators::initial_time(ti_str) {
+		Ok(ti) => ti,
+		Err(message) => return Err(message),
+	};
 	let xi = match helper::parse_expression(xi_str.to_string()) {
 	  	Ok(x0) => x0,
 	  	Err(message) => return Err(message),
@@ -51,35 +90,117 @@ pub fn raw (xi_str: &RawStr, vi_str: &RawStr, tf_str: &RawStr, nt_str: &RawStr,
 		},
 		Err(message) => return Err(message),
   	};
-	let mut xs = vec![xi];
-	let mut vs = vec![vi];
-	let dt = tf / (nt as f64);
-	for i in 0..nt {
-		let t = (i as f64) * tf / (nt as f64);
-		let x = xs[i as usize];
-		let v = vs[i as usize];
-		let v1 = v;
-		let a1 = match helper::function3(input_str.to_string(), x, t, v) {
-			Ok(a) => a,
-			Err(message) => return Err(message),
-		};
-		let v2 = v + a1 * dt / 2.;
-		let a2 = match helper::function3(input_str.to_string(), x + v * dt / 2., t + dt / 2., v2) {
-			Ok(a) => a,
-			Err(message) => return Err(message),
-		};
-		let v3 = v + a2 * dt / 2.;
-		let a3 = match helper::function3(input_str.to_string(), x + v2 * dt / 2., t + dt / 2., v3) {
-			Ok(a) => a,
+	let period = match period_str {
+		Some(period_str) => match helper::parse_expression(period_str.to_string()) {
+			Ok(period) => {
+				if period <= 0. {
+					return Err(format!("Error: the period must be positive, unlike {}.", period));
+				}
+				Some(period)
+			},
 			Err(message) => return Err(message),
-		};
-		let v4 = v + a3 * dt;
-		let a4 = match helper::function3(input_str.to_string(), x + v3 * dt, t + dt, v4) {
-			Ok(a) => a,
+		},
+		None => None,
+	};
+	let lyapunov = match lyapunov_str.map(|lyapunov_str| lyapunov_str.to_lowercase()) {
+		None => false,
+		Some(lyapunov_str) => match lyapunov_str.as_str() {
+			"true" => true,
+			"false" => false,
+			_ => return Err(format!("Error: lyapunov must be either 'true' or 'false', not '{}'.", lyapunov_str)),
+		},
+	};
+	// The equation is equivalent to the system dx/dt = v and dv/dt = function of x, v, and t.
+	let f = |t: f64, y: &[f64]| match helper::function3(input_str.to_string(), y[0], t, y[1]) {
+		Ok(a) => Ok(vec![y[1], a]),
+		Err(message) => Err(message),
+	};
+	let names: Vec<String> = ["x", "v", "t"].iter().map(|name| name.to_string()).collect();
+	let events = match integrators::events(events_str, terminal_str, &names) {
+		Ok(events) => events,
+		Err(message) => return Err(message),
+	};
+	let solution = match integrators::solve(&f, ti, &[xi, vi], tf, nt, &settings, &events) {
+		Ok(solution) => solution,
+		Err(message) => return Err(message),
+	};
+	let energies = match energy_str {
+		Some(energy_str) => {
+			let mut energy = energy_str.to_lowercase();
+			for (code, symbol) in [("%5e", "^"), ("%20", "")] {
+				energy = str::replace(&energy, code, symbol);
+			}
+			let mut energies = vec![];
+			for (t, y) in solution.ts.iter().zip(&solution.ys) {
+				energies.push(match helper::function_n(&energy, &names, &[y[0], y[1], *t]) {
+					Ok(energy) => energy,
+					Err(message) => return Err(message),
+				});
+			}
+			Some(energies)
+		},
+		None => None,
+	};
+	// largest deviation of the energy from its initial value
+	let drift = energies.as_ref().map(|energies| energies.iter().fold(0., |max: f64, energy| max.max((energy - energies[0]).abs())));
+	// Poincaré section, ie the solution at each multiple of the period after the initial time (until the integration stops)
+	let section = match period {
+		Some(period) => {
+			let t_last = solution.ts[solution.ts.len() - 1];
+			let direction = if tf < ti {-1.} else {1.};
+			let count = ((t_last - ti).abs() / period).floor() as usize;
+			let times: Vec<f64> = (0..=count).map(|k| ti + direction * (k as f64) * period).collect();
+			let ys = integrators::sample(&solution.steps, &[xi, vi], &times);
+			Some(times.iter().zip(&ys).map(|(t, y)| [*t, y[0], y[1]]).collect())
+		},
+		None => None,
+	};
+	let lyapunov = if lyapunov {
+		match integrators::lyapunov(&f, ti, &[xi, vi], tf, nt, &settings) {
+			Ok(lyapunov) => Some(lyapunov),
 			Err(message) => return Err(message),
-		};
-		xs.push(x + ((v1 + v4) + 2. * (v2 + v3)) * dt / 6.);
-		vs.push(v + ((a1 + a4) + 2. * (a2 + a3)) * dt / 6.);
+		}
+	} else {
+		None
+	};
+	let adaptive = settings.adaptive();
+	return Ok(Results {
+		ti,
+		xi,
+		vi,
+		tf,
+		nt,
+		ts: solution.ts,
+		xs: solution.ys.iter().map(|y| y[0]).collect(),
+		vs: solution.ys.iter().map(|y| y[1]).collect(),
+		method: settings.method,
+		rtol: if adaptive {Some(settings.rtol)} else {None},
+		atol: if adaptive {Some(settings.atol)} else {None},
+		accepted: solution.accepted,
+		rejected: solution.rejected,
+		switched: solution.switched,
+		events: solution.events,
+		energies,
+		drift,
+		section,
+		lyapunov,
+	});
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// d²x/dt² = -x with x = cos(t) and v = -sin(t) at t = 1, solved forward and backward from there
+	#[test]
+	fn nonzero_initial_time() {
+		for (tf, method) in [("3", "rk4"), ("-1", "rk4"), ("3", "rk45"), ("-1", "rk45"), ("3", "yoshida4"), ("-1", "yoshida4")] {
+			let results = raw(RawStr::from_str("cos(1)"), RawStr::from_str("-sin(1)"), RawStr::from_str(tf), RawStr::from_str("20"), RawStr::from_str("-x"), Some("1"), Some(method), None, None, None, None, None, None, None).unwrap();
+			assert_eq!(results.ts[0], 1.);
+			assert_eq!(*results.ts.last().unwrap(), results.tf);
+			for ((t, x), v) in results.ts.iter().zip(&results.xs).zip(&results.vs) {
+				assert!((x - t.cos()).abs() < 1e-4 && (v + t.sin()).abs() < 1e-4, "{} to t = {}: x({}) = {}, v = {}", method, tf, t, x, v);
+			}
+		}
 	}
-	return Ok(Results {xi, vi, tf, nt, xs, vs});
 }
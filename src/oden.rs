@@ -0,0 +1,139 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::integrators;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "DIFFERENTIAL EQUATIONS OF ANY ORDER".to_string(),
+		links:  calculus::links(18),
+		instructions: format!("This page solves a differential equation of the form <i>d<sup>n</sup>x/dt<sup>n</sup></I> = function of <I>t</I>, of <I>x</I>, and of its first <i>n</i> - 1 derivatives, with a specified 'initial condition', ie values of <I>x</I> and of these derivatives when the 'time' <i>t</i> = 0 (by default).  In these functions <i>x</i><sub>1</sub> (typed as <tt>x1</tt>) is <i>dx/dt</i>, <i>x</i><sub>2</sub> (typed as <tt>x2</tt>) is <i>d</i><sup>2</sup><i>x/dt</i><sup>2</sup>, and so on.  In the url bar after <tt>'https://basic-calculus.herokuapp.com/oden</tt> type the following:<p align=center>&sol;&lt;order <i>n</i> of the equation&gt;&sol;&lt;comma-separated initial values of <i>x</I>, <i>x</i><sub>1</sub>, ..., <i>x</i><sub><i>n</i>-1</sub>&gt;&sol;&lt;final value of <i>t</I>&gt;&sol;&lt;number of time-steps&gt;&sol;&lt;function of <i>t</I>, <i>x</I>, <i>x</i><sub>1</sub>, ..., <i>x</i><sub><i>n</i>-1</sub>&gt;</tt></p>{}  {}  The symplectic methods of the 2nd-order ODE page may also be used if <i>n</i> = 2.  {}", integrators::INITIAL_TIME, integrators::METHODS, integrators::EVENTS),
+		note: format!("{}{}", helper::NOTE1, helper::NOTE2).to_string(),
+		example: "To solve the 'jerk' equation d<sup>3</sup>x/dt<sup>3</sup> = -x from t = 0 to t = 1 using 10 time-steps and the initial conditions that x(0) = 1, dx/dt(0) = -1, and d<sup>2</sup>x/dt<sup>2</sup>(0) = 1 (for which the solution is exp(-t)), type <tt>/3/1,-1,1/1/10/-x</tt> after /oden in the url above.  The final result should be that x(1) = 0.3678....  For the deflection of a uniformly loaded beam, d<sup>4</sup>x/dt<sup>4</sup> = 1 with x and its first three derivatives vanishing at t = 0, typing <tt>/4/0,0,0,0/2/10/1</tt> should give x(2) = 2<sup>4</sup>/24 = 0.6666....".to_string(),
+		algorithm: format!("The equation is rewritten as a system of <i>n</i> 1st-order equations for <i>x</i>, <i>x</i><sub>1</sub>, ..., <i>x</i><sub><i>n</i>-1</sub>, the last of which is the given equation and each other of which states that the derivative of one of these is the next.  This system is then solved as on the page for systems of 1st-order differential equations.  {}", integrators::ALGORITHM),
+		json: "Type '/json' in the url bar immediately after 'oden' if you would like the result in this format rather than html.  A successful response will contain fourteen properties. 'order' is the order of the equation, 'ti' is the initial time, 'xi' is the array of the initial values of <i>x</i> and its derivatives, 'tf' and 'nt' are the final time and number of time-steps, 'ts' is the array of output times, 'xs' is the array of the values of <i>x</i> and its derivatives at those times, 'method' is the integration method, 'rtol' and 'atol' are the tolerances of the adaptive method (or null for a fixed-step method), 'accepted' and 'rejected' are the numbers of steps which were accepted and rejected, 'switched' is the time at which the automatic method switches to the stiff solver (or null), and 'events' is an array of the events which were found, each of which has the properties 'event' (the expression), 't' (the time), and 'y' (the values of <i>x</i> and its derivatives at that time). An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub order: usize,
+	pub ti: f64,
+	pub xi: Vec<f64>,
+	pub tf: f64,
+	pub nt: i32,
+	pub ts: Vec<f64>,
+	pub xs: Vec<Vec<f64>>,
+	pub method: String,
+	pub rtol: Option<f64>,
+	pub atol: Option<f64>,
+	pub accepted: i32,
+	pub rejected: i32,
+	pub switched: Option<f64>,
+	pub events: Vec<integrators::Event>,
+}
+
+pub fn raw(
+	order_str: &RawStr,
+	xi_str: &RawStr,
+	tf_str: &RawStr,
+	nt_str: &RawStr,
+	input_str: &RawStr,
+	ti_str: Option<&str>,
+	method: Option<&str>,
+	rtol_str: Option<&str>,
+	atol_str: Option<&str>,
+	events_str: Option<&str>,
+	terminal_str: Option<&str>,
+) -> Result<Results, String> {
+	let order = match helper::parse_expression(order_str.to_string()) {
+		Ok(order) => {
+			if order.round() != order || order <= 0. {
+				return Err(format!("Error: the order must be a positive integer, unlike {}.", order));
+			}
+			order as usize
+		},
+		Err(message) => return Err(message),
+	};
+	let settings = match integrators::settings(method, rtol_str, atol_str) {
+		Ok(settings) => settings,
+		Err(message) => return Err(message),
+	};
+	if settings.symplectic() && order != 2 {
+		return Err(format!("Error: the {} method applies only to 2nd-order equations.", settings.method));
+	}
+	let ti = match integrators::initial_time(ti_str) {
+		Ok(ti) => ti,
+		Err(message) => return Err(message),
+	};
+	let mut xi = vec![];
+	for x_str in helper::parse_list(xi_str) {
+		xi.push(match helper::parse_expression(x_str) {
+			Ok(x) => x,
+			Err(message) => return Err(message),
+		});
+	}
+	if xi.len() != order {
+		return Err(format!("Error: an equation of order {} requires {} initial values, not {}.", order, order, xi.len()));
+	}
+	let tf = match helper::parse_expression(tf_str.to_string()) {
+		Ok(tf) => tf,
+		Err(message) => return Err(message),
+	};
+	let nt = match helper::parse_expression(nt_str.to_string()) {
+		Ok(nt) => {
+			if nt.round() != nt {
+				return Err(format!("{} is not an integer.", nt));
+			} else if nt <= 0. {
+				return Err("Number of timesteps must be positive.".to_string());
+			}
+			nt as i32
+		},
+		Err(message) => return Err(message),
+	};
+	// x, its derivatives x1, x2, ..., and the time
+	let mut names = vec!["x".to_string()];
+	for j in 1..order {
+		names.push(format!("x{}", j));
+	}
+	names.push("t".to_string());
+	// The derivative of each of x, x1, ... is the next, and that of the last is the given function.
+	let f = |t: f64, y: &[f64]| -> Result<Vec<f64>, String> {
+		let mut point = y.to_vec();
+		point.push(t);
+		let mut dy = y[1..].to_vec();
+		dy.push(match helper::function_n(input_str, &names, &point) {
+			Ok(dy) => dy,
+			Err(message) => return Err(message),
+		});
+		Ok(dy)
+	};
+	let events = match integrators::events(events_str, terminal_str, &names) {
+		Ok(events) => events,
+		Err(message) => return Err(message),
+	};
+	let solution = match integrators::solve(&f, ti, &xi, tf, nt, &settings, &events) {
+		Ok(solution) => solution,
+		Err(message) => return Err(message),
+	};
+	let adaptive = settings.adaptive();
+	Ok(Results {
+		order,
+		ti,
+		xi,
+		tf,
+		nt,
+		ts: solution.ts,
+		xs: solution.ys,
+		method: settings.method,
+		rtol: if adaptive {Some(settings.rtol)} else {None},
+		atol: if adaptive {Some(settings.atol)} else {None},
+		accepted: solution.accepted,
+		rejected: solution.rejected,
+		switched: solution.switched,
+		events: solution.events,
+	})
+}
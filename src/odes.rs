@@ -0,0 +1,135 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::integrators;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "SYSTEMS OF 1ST-ORDER DIFFERENTIAL EQUATIONS".to_string(),
+		links:  calculus::links(15),
+		instructions: format!("This page solves <i>n</i> simultaneous differential equations of the form <i>dy<sub>i</sub>/dt</I> = function of <I>t</I> and of the <i>n</i> variables <i>y</i><sub>1</sub>, ..., <i>y<sub>n</sub></i>, with a specified 'initial condition', ie values of the variables when the 'time' <i>t</i> = 0 (by default).  In the url bar after <tt>'https://basic-calculus.herokuapp.com/odes</tt> type the following:<p align=center>&sol;&lt;comma-separated names of the variables&gt;&sol;&lt;comma-separated initial values of the variables&gt;&sol;&lt;final value of <i>t</I>&gt;&sol;&lt;number of time-steps&gt;&sol;&lt;comma-separated functions of the variables and <i>t</I>&gt;</tt></p>Each name must start with a letter and may contain letters, digits, and underscores, and <i>t</i> may not be the name of a variable.  The <i>i</i>-th function is the time-derivative of the <i>i</i>-th variable.  {}  {}  {}", integrators::INITIAL_TIME, integrators::METHODS, integrators::EVENTS),
+		note: format!("{}{}", helper::NOTE1, helper::NOTE2).to_string(),
+		example: "To solve the predator-prey equations dx/dt = x - xy and dy/dt = xy - y from t = 0 to t = 10 using 100 time-steps and the initial conditions that x(0) = 2 and y(0) = 1, type <tt>/x,y/2,1/10/100/x-x*y,x*y-y</tt> after /odes in the url above.  The final result should be that x(10) = 0.450... and y(10) = 0.695....  Other examples are the SIR model of an epidemic (<tt>/s,i,r/0.99,0.01,0/50/100/-0.5s*i,0.5s*i-0.1i,0.1i</tt>) and a pair of coupled oscillators (<tt>/x1,v1,x2,v2/1,0,0,0/20/200/v1,-2x1+x2,v2,-2x2+x1</tt>).".to_string(),
+		algorithm: integrators::ALGORITHM.to_string(),
+		json: "Type '/json' in the url bar immediately after 'odes' if you would like the result in this format rather than html.  A successful response will contain fourteen properties. 'ti' is the initial time, 'vars' is the list of names of the variables, 'xi' is their initial values, 'tf' and 'nt' are the final time and number of time-steps, 'ts' is the array of output times, 'ys' is the array of the variables' values at those times, 'method' is the integration method, 'rtol' and 'atol' are the tolerances of the adaptive method (or null for a fixed-step method), 'accepted' and 'rejected' are the numbers of steps which were accepted and rejected, and 'switched' is the time at which the automatic method switches to the stiff solver (or null), and 'events' is an array of the events which were found, each of which has the properties 'event' (the expression), 't' (the time), and 'y' (the variables' values at that time). An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub ti: f64,
+	pub vars: Vec<String>,
+	pub xi: Vec<f64>,
+	pub tf: f64,
+	pub nt: i32,
+	pub ts: Vec<f64>,
+	pub ys: Vec<Vec<f64>>,
+	pub method: String,
+	pub rtol: Option<f64>,
+	pub atol: Option<f64>,
+	pub accepted: i32,
+	pub rejected: i32,
+	pub switched: Option<f64>,
+	pub events: Vec<integrators::Event>,
+}
+
+pub fn raw(
+	vars_str: &RawStr,
+	xi_str: &RawStr,
+	tf_str: &RawStr,
+	nt_str: &RawStr,
+	input_str: &RawStr,
+	ti_str: Option<&str>,
+	method: Option<&str>,
+	rtol_str: Option<&str>,
+	atol_str: Option<&str>,
+	events_str: Option<&str>,
+	terminal_str: Option<&str>,
+) -> Result<Results, String> {
+	let settings = match integrators::settings(method, rtol_str, atol_str) {
+		Ok(settings) => settings,
+		Err(message) => return Err(message),
+	};
+	if settings.symplectic() {
+		return Err(format!("Error: the {} method applies only to 2nd-order equations.", settings.method));
+	}
+	let ti = match integrators::initial_time(ti_str) {
+		Ok(ti) => ti,
+		Err(message) => return Err(message),
+	};
+	let vars = match helper::parse_names(vars_str) {
+		Ok(vars) => vars,
+		Err(message) => return Err(message),
+	};
+	if vars.contains(&"t".to_string()) {
+		return Err("Error: 't' is the time, so it cannot be the name of a variable.".to_string());
+	}
+	let mut xi = vec![];
+	for x_str in helper::parse_list(xi_str) {
+		xi.push(match helper::parse_expression(x_str) {
+			Ok(x) => x,
+			Err(message) => return Err(message),
+		});
+	}
+	let expressions = helper::parse_list(input_str);
+	if xi.len() != vars.len() || expressions.len() != vars.len() {
+		return Err(format!("Error: there are {} variables, {} initial values, and {} functions, but these numbers must be equal.", vars.len(), xi.len(), expressions.len()));
+	}
+	let tf = match helper::parse_expression(tf_str.to_string()) {
+		Ok(tf) => tf,
+		Err(message) => return Err(message),
+	};
+	let nt = match helper::parse_expression(nt_str.to_string()) {
+		Ok(nt) => {
+			if nt.round() != nt {
+				return Err(format!("{} is not an integer.", nt));
+			} else if nt <= 0. {
+				return Err("Number of timesteps must be positive.".to_string());
+			}
+			nt as i32
+		},
+		Err(message) => return Err(message),
+	};
+	let mut names = vars.clone();
+	names.push("t".to_string());
+	let f = |t: f64, y: &[f64]| -> Result<Vec<f64>, String> {
+		let mut point = y.to_vec();
+		point.push(t);
+		let mut dy = vec![];
+		for expression in &expressions {
+			dy.push(match helper::function_n(expression, &names, &point) {
+				Ok(dy) => dy,
+				Err(message) => return Err(message),
+			});
+		}
+		Ok(dy)
+	};
+	let events = match integrators::events(events_str, terminal_str, &names) {
+		Ok(events) => events,
+		Err(message) => return Err(message),
+	};
+	let solution = match integrators::solve(&f, ti, &xi, tf, nt, &settings, &events) {
+		Ok(solution) => solution,
+		Err(message) => return Err(message),
+	};
+	let adaptive = settings.adaptive();
+	Ok(Results {
+		ti,
+		vars,
+		xi,
+		tf,
+		nt,
+		ts: solution.ts,
+		ys: solution.ys,
+		method: settings.method,
+		rtol: if adaptive {Some(settings.rtol)} else {None},
+		atol: if adaptive {Some(settings.atol)} else {None},
+		accepted: solution.accepted,
+		rejected: solution.rejected,
+		switched: solution.switched,
+		events: solution.events,
+	})
+}
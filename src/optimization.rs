@@ -0,0 +1,265 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::linear_algebra;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "MULTIVARIABLE OPTIMIZATION".to_string(),
+		links:  calculus::links(11),
+		instructions: "This page finds a local maximum or minimum of a function of several variables.  In the url bar after <tt>'https://basic-calculus.herokuapp.com/optimization</tt> type the following:<p align=center>&sol;&lt;comma-separated names of the variables&gt;&sol;&lt;comma-separated values at which to start the search&gt;&sol;&lt;function of the variables&gt;</tt></p>Each name must start with a letter and may contain letters, digits, and underscores.  By default a minimum is found with the BFGS method.  To find a maximum, append <tt>?mode=max</tt> to the url.  To use the Nelder-Mead method, append <tt>?method=nelder-mead</tt> (or <tt>&method=nelder-mead</tt> if the url already contains a '?').  Note that this will not necessarily find the extremum which is <i>closest</i> to the starting point.".to_string(),
+		note: format!("{}{}", helper::NOTE1, helper::NOTE2).to_string(),
+		example: "To find the minimum of Rosenbrock's function (1 - <i>x</i>)<sup>2</sup> + 100(<i>y</i> - <i>x</i><sup>2</sup>)<sup>2</sup> while starting the search at (<i>x</i>, <i>y</i>) = (-1, 2), type <tt>/x,y/-1,2/(1-x)**2+100(y-x**2)**2</tt> after the current url address.  The result for this should be that the minimum value 0 occurs at (1, 1).".to_string(),
+		algorithm: "either the Broyden-Fletcher-Goldfarb-Shanno (BFGS) quasi-Newton method, which uses a gradient calculated by central differences and a backtracking line search, or the derivative-free Nelder-Mead (downhill simplex) method".to_string(),
+		json: "Type '/json' in the url bar immediately after 'optimization' if you would like the result in this format rather than html.  A successful response will contain ten properties. 'method' is either 'bfgs' or 'nelder-mead', 'mode' is either 'min' or 'max', 'vars' is the list of names of the variables, 'xi' is the point where the search starts, 'x' is where the search ends, 'f' is the function value there, 'gradient_norm' is the magnitude of the function's gradient there, 'steps' is the number of iterations required, 'history' is an array of the point ('x') and function value ('f') after each iteration, and 'epsilon' is the relative accuracy to which the function value is converged. An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Point {
+	pub x: Vec<f64>,
+	pub f: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub method: String,
+	pub mode: String,
+	pub vars: Vec<String>,
+	pub xi: Vec<f64>,
+	pub x: Vec<f64>,
+	pub f: f64,
+	pub gradient_norm: f64,
+	pub steps: i32,
+	pub history: Vec<Point>,
+	pub epsilon: f64,
+}
+
+// central-difference approximation of the gradient
+pub fn gradient<F: Fn(&[f64]) -> Result<f64, String>>(g: &F, x: &[f64]) -> Result<Vec<f64>, String> {
+	let mut grad = vec![];
+	for j in 0..x.len() {
+		let dx = 1e-5 * x[j].abs().max(1.);
+		let mut x_plus = x.to_vec();
+		let mut x_minus = x.to_vec();
+		x_plus[j] += dx;
+		x_minus[j] -= dx;
+		let g_plus = match g(&x_plus) {
+			Ok(g_plus) => g_plus,
+			Err(message) => return Err(message),
+		};
+		let g_minus = match g(&x_minus) {
+			Ok(g_minus) => g_minus,
+			Err(message) => return Err(message),
+		};
+		grad.push((g_plus - g_minus) / 2. / dx);
+	}
+	Ok(grad)
+}
+
+// Minimize g, starting from xi, with the quasi-Newton method of Broyden, Fletcher, Goldfarb, and Shanno.
+pub fn bfgs<F: Fn(&[f64]) -> Result<f64, String>>(g: &F, xi: &[f64], epsilon: f64) -> Result<Vec<Point>, String> {
+	let steps_max = 500;
+	let n = xi.len();
+	let mut x = xi.to_vec();
+	let mut gx = match g(&x) {
+		Ok(gx) => gx,
+		Err(message) => return Err(message),
+	};
+	let mut grad = match gradient(g, &x) {
+		Ok(grad) => grad,
+		Err(message) => return Err(message),
+	};
+	// approximation of the inverse of the Hessian matrix
+	let identity: Vec<Vec<f64>> = (0..n).map(|i| (0..n).map(|j| if i == j {1.} else {0.}).collect()).collect();
+	let mut h = identity.clone();
+	let mut history = vec![Point {x: x.clone(), f: gx}];
+	for _ in 0..steps_max {
+		let mut p: Vec<f64> = h.iter().map(|row| -linear_algebra::dot(row, &grad)).collect();
+		let mut slope = linear_algebra::dot(&p, &grad);
+		if slope >= 0. {
+			// The approximate Hessian has lost positive definiteness, so restart with steepest descent.
+			h = identity.clone();
+			p = grad.iter().map(|gj| -gj).collect();
+			slope = linear_algebra::dot(&p, &grad);
+		}
+		// backtracking line search, which requires sufficient decrease (Armijo's condition)
+		let mut alpha = 1.;
+		let mut found = None;
+		while alpha > 1e-12 {
+			let x_new: Vec<f64> = x.iter().zip(&p).map(|(xj, pj)| xj + alpha * pj).collect();
+			// Allow the line search to step back from points where g cannot be evaluated.
+			if let Ok(g_new) = g(&x_new) {
+				if g_new <= gx + 1e-4 * alpha * slope {
+					found = Some((x_new, g_new));
+					break;
+				}
+			}
+			alpha /= 2.;
+		}
+		let (x_new, g_new) = match found {
+			Some(found) => found,
+			// No further decrease is possible, to within the accuracy of the gradient.
+			None => return Ok(history),
+		};
+		let grad_new = match gradient(g, &x_new) {
+			Ok(grad_new) => grad_new,
+			Err(message) => return Err(message),
+		};
+		let s: Vec<f64> = x_new.iter().zip(&x).map(|(a, b)| a - b).collect();
+		let y: Vec<f64> = grad_new.iter().zip(&grad).map(|(a, b)| a - b).collect();
+		let sy = linear_algebra::dot(&s, &y);
+		// Skip the update if it would not preserve positive definiteness.
+		if sy > 1e-12 * linear_algebra::norm(&s) * linear_algebra::norm(&y) {
+			// H <- (I - s y^T / sy) H (I - y s^T / sy) + s s^T / sy
+			let hy: Vec<f64> = h.iter().map(|row| linear_algebra::dot(row, &y)).collect();
+			let yhy = linear_algebra::dot(&y, &hy);
+			for i in 0..n {
+				for j in 0..n {
+					h[i][j] += (sy + yhy) * s[i] * s[j] / sy / sy - (hy[i] * s[j] + s[i] * hy[j]) / sy;
+				}
+			}
+		}
+		let change = (gx - g_new).abs();
+		x = x_new;
+		gx = g_new;
+		grad = grad_new;
+		history.push(Point {x: x.clone(), f: gx});
+		if change <= epsilon * (gx.abs() + epsilon) && linear_algebra::norm(&s) <= epsilon.sqrt() * (1. + linear_algebra::norm(&x)) {
+			return Ok(history);
+		}
+	}
+	Err(format!("Unable to converge within {} steps.", steps_max))
+}
+
+// Minimize g, starting from xi, by the Nelder-Mead (downhill simplex) method.
+pub fn nelder_mead<F: Fn(&[f64]) -> Result<f64, String>>(g: &F, xi: &[f64], epsilon: f64) -> Result<Vec<Point>, String> {
+	let steps_max = 10000;
+	let n = xi.len();
+	// initial simplex, stepping from xi along each axis
+	let mut simplex = vec![];
+	for j in 0..=n {
+		let mut x = xi.to_vec();
+		if j > 0 {
+			x[j - 1] += if x[j - 1] == 0. {0.1} else {0.1 * x[j - 1].abs()};
+		}
+		let f = match g(&x) {
+			Ok(f) => f,
+			Err(message) => return Err(message),
+		};
+		simplex.push(Point {x, f});
+	}
+	let mut history = vec![];
+	for _ in 0..steps_max {
+		simplex.sort_by(|a, b| a.f.partial_cmp(&b.f).unwrap_or(std::cmp::Ordering::Equal));
+		history.push(simplex[0].clone());
+		let size = (1..=n).map(|i| {
+			let dx: Vec<f64> = simplex[i].x.iter().zip(&simplex[0].x).map(|(a, b)| a - b).collect();
+			linear_algebra::norm(&dx)
+		}).fold(0., f64::max);
+		if simplex[n].f - simplex[0].f <= epsilon * (simplex[0].f.abs() + simplex[n].f.abs() + epsilon) && size <= epsilon.sqrt() * (1. + linear_algebra::norm(&simplex[0].x)) {
+			return Ok(history);
+		}
+		// centroid of all points except the worst one
+		let centroid: Vec<f64> = (0..n).map(|j| simplex[..n].iter().map(|point| point.x[j]).sum::<f64>() / (n as f64)).collect();
+		// point along the line from the worst point thru the centroid
+		let along = |factor: f64| -> Result<Point, String> {
+			let x: Vec<f64> = centroid.iter().zip(&simplex[n].x).map(|(c, w)| c + factor * (c - w)).collect();
+			match g(&x) {
+				Ok(f) => Ok(Point {x, f}),
+				Err(message) => Err(message),
+			}
+		};
+		let reflected = match along(1.) {
+			Ok(reflected) => reflected,
+			Err(message) => return Err(message),
+		};
+		if reflected.f < simplex[0].f {
+			let expanded = match along(2.) {
+				Ok(expanded) => expanded,
+				Err(message) => return Err(message),
+			};
+			simplex[n] = if expanded.f < reflected.f {expanded} else {reflected};
+			continue;
+		}
+		if reflected.f < simplex[n - 1].f {
+			simplex[n] = reflected;
+			continue;
+		}
+		let contracted = match along(if reflected.f < simplex[n].f {0.5} else {-0.5}) {
+			Ok(contracted) => contracted,
+			Err(message) => return Err(message),
+		};
+		if contracted.f < simplex[n].f.min(reflected.f) {
+			simplex[n] = contracted;
+			continue;
+		}
+		// Shrink the simplex toward its best point.
+		for i in 1..=n {
+			let x: Vec<f64> = simplex[i].x.iter().zip(&simplex[0].x).map(|(a, b)| (a + b) / 2.).collect();
+			let f = match g(&x) {
+				Ok(f) => f,
+				Err(message) => return Err(message),
+			};
+			simplex[i] = Point {x, f};
+		}
+	}
+	Err(format!("Unable to converge within {} steps.", steps_max))
+}
+
+pub fn raw(vars_str: &RawStr, xi_str: &RawStr, input_str: &RawStr, method: &str, mode: &str) -> Result<Results, String> {
+	let epsilon = (10_f64).powf(-12.);
+	// Finding a maximum of f is equivalent to finding a minimum of -f.
+	let sign = match mode {
+		"max" => -1.,
+		"min" => 1.,
+		_ => return Err(format!("Error: the mode must be either 'max' or 'min', not '{}'.", mode)),
+	};
+	let vars = match helper::parse_names(vars_str) {
+		Ok(vars) => vars,
+		Err(message) => return Err(message),
+	};
+	let mut xi = vec![];
+	for x_str in helper::parse_list(xi_str) {
+		xi.push(match helper::parse_expression(x_str) {
+			Ok(x) => x,
+			Err(message) => return Err(message),
+		});
+	}
+	if xi.len() != vars.len() {
+		return Err(format!("Error: there are {} variables but {} starting values.", vars.len(), xi.len()));
+	}
+	let g = |x: &[f64]| helper::function_n(input_str, &vars, x).map(|f| sign * f);
+	let history = match method {
+		"bfgs" => bfgs(&g, &xi, epsilon),
+		"nelder-mead" => nelder_mead(&g, &xi, epsilon),
+		_ => return Err(format!("Error: the method must be either 'bfgs' or 'nelder-mead', not '{}'.", method)),
+	};
+	let mut history = match history {
+		Ok(history) => history,
+		Err(message) => return Err(message),
+	};
+	for point in &mut history {
+		point.f *= sign;
+	}
+	let last = history[history.len() - 1].clone();
+	let gradient_norm = match gradient(&g, &last.x) {
+		Ok(grad) => linear_algebra::norm(&grad),
+		Err(message) => return Err(message),
+	};
+	Ok(Results {
+		method: method.to_string(),
+		mode: mode.to_string(),
+		vars,
+		xi,
+		x: last.x,
+		f: last.f,
+		gradient_norm,
+		steps: history.len() as i32 - 1,
+		history,
+		epsilon,
+	})
+}
@@ -0,0 +1,225 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::integrators;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "PARTIAL DIFFERENTIAL EQUATIONS".to_string(),
+		links:  calculus::links(21),
+		instructions: format!("This page solves either the 'heat' (or diffusion) equation &part;<i>u</i>/&part;<i>t</i> = <i>D</i> &part;<sup>2</sup><i>u</i>/&part;<i>x</i><sup>2</sup> + <i>f</i> or the 'wave' equation &part;<sup>2</sup><i>u</i>/&part;<i>t</i><sup>2</sup> = <i>c</i><sup>2</sup> &part;<sup>2</sup><i>u</i>/&part;<i>x</i><sup>2</sup> + <i>f</i> for a function <i>u</i> of <i>x</i> and <i>t</i> over an interval of <i>x</i>, where the 'source' <i>f</i> is a function of <i>u</i>, <i>x</i>, and <i>t</i> (which by default vanishes).  At each end of the interval either <i>u</i> keeps its initial value (a 'Dirichlet' condition) or &part;<i>u</i>/&part;<i>x</i> vanishes (a 'Neumann' condition).  In the url bar after <tt>'https://basic-calculus.herokuapp.com/pde</tt> type the following:<p align=center>&sol;&lt;<tt>heat</tt> or <tt>wave</tt>&gt;&sol;&lt;diffusivity <i>D</i> or speed <i>c</i>&gt;&sol;&lt;lower end of the interval&gt;&sol;&lt;upper end of the interval&gt;&sol;&lt;number of spatial intervals&gt;&sol;&lt;initial value of <i>u</i>, a function of <i>x</I>&gt;&sol;&lt;final value of <i>t</I>&gt;&sol;&lt;number of time-steps&gt;</tt></p>By default the boundary conditions are Dirichlet at both ends, and the initial velocity &part;<i>u</i>/&part;<i>t</i> of a wave vanishes.  A source may be specified by appending for instance <tt>?source=u(1-u)</tt> to the url, Neumann conditions may be specified by appending <tt>left=neumann</tt> and/or <tt>right=neumann</tt>, and a wave's initial velocity may be specified by appending for instance <tt>velocity=sin(PI*x)</tt> (a function of <i>x</i>).  {}  The methods of the page for systems of 1st-order differential equations may be chosen as explained there, and (for the wave equation) the symplectic methods <tt>verlet</tt>, <tt>leapfrog</tt>, and <tt>yoshida4</tt> may also be chosen.  The default method is <tt>auto</tt> for the heat equation (which becomes stiff when the spatial intervals are short) and <tt>rk45</tt> for the wave equation.  For an adaptive method the number of time-steps is only the number of intervals at which the solution is reported.", integrators::INITIAL_TIME),
+		note: format!("{}{}", helper::NOTE1, helper::NOTE2).to_string(),
+		example: "To solve the heat equation with <i>D</i> = 1 over the interval 0 &lt; x &lt; 1, divided into 20 parts, from t = 0 to t = 0.1 using 10 time-steps and the initial condition that u = sin(&pi;x), type <tt>/heat/1/0/1/20/sin(PI*x)/0.1/10</tt> after /pde in the url above.  The final result at x = 0.5 should be close to exp(-&pi;<sup>2</sup>/10) = 0.3727...; the difference (0.0007...) is due to the discretization of <i>x</i> and decreases 4-fold if the number of parts is doubled.  For a plucked string, typing <tt>/wave/1/0/1/20/sin(PI*x)/1/10</tt> should give a final result close to -sin(&pi;x), ie half a period of oscillation.  For the insulated ends of a rod, typing <tt>/heat/1/0/1/20/cos(PI*x)/0.1/10?left=neumann&right=neumann</tt> should give a final result close to exp(-&pi;<sup>2</sup>/10)cos(&pi;x).".to_string(),
+		algorithm: format!("The 'method of lines' is used: <i>x</i> is divided into intervals of equal length <i>h</i>, and &part;<sup>2</sup><i>u</i>/&part;<i>x</i><sup>2</sup> at each point is approximated by the 2nd-order central difference (<i>u</i><sub>left</sub> - 2<i>u</i> + <i>u</i><sub>right</sub>)/<i>h</i><sup>2</sup> of the values of <i>u</i> at that point and its neighbors.  At an end with a Neumann condition, the missing neighbor is replaced by the mirror image of the neighbor inside the interval.  At an end with a Dirichlet condition, <i>u</i> does not change.  This yields a system of 1st-order differential equations (for the values of <i>u</i> and, for the wave equation, of &part;<i>u</i>/&part;<i>t</i> at the points), which is solved as on the page for systems of 1st-order differential equations.  {}", integrators::ALGORITHM),
+		json: "Type '/json' in the url bar immediately after 'pde' if you would like the result in this format rather than html.  A successful response will contain eighteen properties. 'equation' is either 'heat' or 'wave', 'coefficient' is the diffusivity or speed, 'a' and 'b' are the ends of the interval, 'left' and 'right' are the boundary conditions there (either 'dirichlet' or 'neumann'), 'ti' is the initial time, 'tf' and 'nt' are the final time and number of time-steps, 'xs' is the array of points, 'ts' is the array of output times, 'us' is the array of the arrays of the values of <i>u</i> at those points at each output time, 'method' is the integration method, 'rtol' and 'atol' are the tolerances of the adaptive method (or null for a fixed-step method), 'accepted' and 'rejected' are the numbers of steps which were accepted and rejected, and 'switched' is the time at which the automatic method switches to the stiff solver (or null). An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub equation: String,
+	pub coefficient: f64,
+	pub a: f64,
+	pub b: f64,
+	pub left: String,
+	pub right: String,
+	pub ti: f64,
+	pub tf: f64,
+	pub nt: i32,
+	pub xs: Vec<f64>,
+	pub ts: Vec<f64>,
+	pub us: Vec<Vec<f64>>,
+	pub method: String,
+	pub rtol: Option<f64>,
+	pub atol: Option<f64>,
+	pub accepted: i32,
+	pub rejected: i32,
+	pub switched: Option<f64>,
+}
+
+// Is the boundary condition a Neumann one (rather than a Dirichlet one)?
+fn neumann(condition: Option<&str>) -> Result<(String, bool), String> {
+	let condition = condition.unwrap_or("dirichlet").to_lowercase();
+	match condition.as_str() {
+		"dirichlet" => Ok((condition, false)),
+		"neumann" => Ok((condition, true)),
+		_ => Err(format!("Error: a boundary condition must be either dirichlet or neumann, not '{}'.", condition)),
+	}
+}
+
+pub fn raw(
+	equation_str: &RawStr,
+	coefficient_str: &RawStr,
+	a_str: &RawStr,
+	b_str: &RawStr,
+	nx_str: &RawStr,
+	initial_str: &RawStr,
+	tf_str: &RawStr,
+	nt_str: &RawStr,
+	source_str: Option<&str>,
+	velocity_str: Option<&str>,
+	left: Option<&str>,
+	right: Option<&str>,
+	ti_str: Option<&str>,
+	method: Option<&str>,
+	rtol_str: Option<&str>,
+	atol_str: Option<&str>,
+) -> Result<Results, String> {
+	let equation = equation_str.to_lowercase();
+	let wave = match equation.as_str() {
+		"heat" => false,
+		"wave" => true,
+		_ => return Err(format!("Error: the equation must be either heat or wave, not '{}'.", equation)),
+	};
+	if !wave && velocity_str.is_some() {
+		return Err("Error: an initial velocity may be specified only for the wave equation.".to_string());
+	}
+	let default = if wave {"rk45"} else {"auto"};
+	let settings = match integrators::settings(Some(method.unwrap_or(default)), rtol_str, atol_str) {
+		Ok(settings) => settings,
+		Err(message) => return Err(message),
+	};
+	if settings.symplectic() && !wave {
+		return Err(format!("Error: the {} method applies only to the wave equation.", settings.method));
+	}
+	let coefficient = match helper::parse_expression(coefficient_str.to_string()) {
+		Ok(coefficient) => {
+			if coefficient <= 0. {
+				return Err(format!("Error: the {} must be positive, unlike {}.", if wave {"speed"} else {"diffusivity"}, coefficient));
+			}
+			coefficient
+		},
+		Err(message) => return Err(message),
+	};
+	let (left, left_neumann) = match neumann(left) {
+		Ok(condition) => condition,
+		Err(message) => return Err(message),
+	};
+	let (right, right_neumann) = match neumann(right) {
+		Ok(condition) => condition,
+		Err(message) => return Err(message),
+	};
+	let a = match helper::parse_expression(a_str.to_string()) {
+		Ok(a) => a,
+		Err(message) => return Err(message),
+	};
+	let b = match helper::parse_expression(b_str.to_string()) {
+		Ok(b) => b,
+		Err(message) => return Err(message),
+	};
+	if b <= a {
+		return Err(format!("Error: the upper end of the interval ({}) must exceed the lower end ({}).", b, a));
+	}
+	let nx = match helper::parse_expression(nx_str.to_string()) {
+		Ok(nx) => {
+			if nx.round() != nx || nx < 2. {
+				return Err(format!("Error: the number of spatial intervals must be an integer which is at least 2, unlike {}.", nx));
+			}
+			nx as usize
+		},
+		Err(message) => return Err(message),
+	};
+	let ti = match integrators::initial_time(ti_str) {
+		Ok(ti) => ti,
+		Err(message) => return Err(message),
+	};
+	let tf = match helper::parse_expression(tf_str.to_string()) {
+		Ok(tf) => tf,
+		Err(message) => return Err(message),
+	};
+	let nt = match helper::parse_expression(nt_str.to_string()) {
+		Ok(nt) => {
+			if nt.round() != nt {
+				return Err(format!("{} is not an integer.", nt));
+			} else if nt <= 0. {
+				return Err("Number of timesteps must be positive.".to_string());
+			}
+			nt as i32
+		},
+		Err(message) => return Err(message),
+	};
+	let h = (b - a) / (nx as f64);
+	let xs: Vec<f64> = (0..=nx).map(|j| if j == nx {b} else {a + (j as f64) * h}).collect();
+	let x_name = ["x".to_string()];
+	let mut yi = vec![];
+	for x in &xs {
+		yi.push(match helper::function_n(initial_str, &x_name, &[*x]) {
+			Ok(u) => u,
+			Err(message) => return Err(message),
+		});
+	}
+	if wave {
+		for (j, x) in xs.iter().enumerate() {
+			let fixed = (j == 0 && !left_neumann) || (j == nx && !right_neumann);
+			yi.push(match velocity_str {
+				Some(velocity_str) if !fixed => match helper::function_n(velocity_str, &x_name, &[*x]) {
+					Ok(v) => v,
+					Err(message) => return Err(message),
+				},
+				_ => 0.,
+			});
+		}
+	}
+	let names: Vec<String> = ["u", "x", "t"].iter().map(|name| name.to_string()).collect();
+	let factor = (if wave {coefficient * coefficient} else {coefficient}) / (h * h);
+	// central difference for the 2nd derivative of u, plus the source
+	let rate = |t: f64, u: &[f64]| -> Result<Vec<f64>, String> {
+		let mut rate = vec![0.; nx + 1];
+		for j in 0..=nx {
+			if (j == 0 && !left_neumann) || (j == nx && !right_neumann) {
+				continue;
+			}
+			// A missing neighbor is the mirror image of the other one.
+			let u_left = if j == 0 {u[1]} else {u[j - 1]};
+			let u_right = if j == nx {u[nx - 1]} else {u[j + 1]};
+			rate[j] = factor * (u_left - 2. * u[j] + u_right);
+			if let Some(source_str) = source_str {
+				rate[j] += match helper::function_n(source_str, &names, &[u[j], xs[j], t]) {
+					Ok(source) => source,
+					Err(message) => return Err(message),
+				};
+			}
+		}
+		Ok(rate)
+	};
+	// For the wave equation the values of u are followed by those of its time-derivative.
+	let f = |t: f64, y: &[f64]| -> Result<Vec<f64>, String> {
+		if !wave {
+			return rate(t, y);
+		}
+		let mut dy = y[nx + 1..].to_vec();
+		dy.extend(match rate(t, &y[..nx + 1]) {
+			Ok(rate) => rate,
+			Err(message) => return Err(message),
+		});
+		Ok(dy)
+	};
+	let solution = match integrators::solve(&f, ti, &yi, tf, nt, &settings, &integrators::Events::none()) {
+		Ok(solution) => solution,
+		Err(message) => return Err(message),
+	};
+	let adaptive = settings.adaptive();
+	Ok(Results {
+		equation,
+		coefficient,
+		a,
+		b,
+		left,
+		right,
+		ti,
+		tf,
+		nt,
+		xs,
+		ts: solution.ts,
+		us: solution.ys.iter().map(|y| y[..nx + 1].to_vec()).collect(),
+		method: settings.method,
+		rtol: if adaptive {Some(settings.rtol)} else {None},
+		atol: if adaptive {Some(settings.atol)} else {None},
+		accepted: solution.accepted,
+		rejected: solution.rejected,
+		switched: solution.switched,
+	})
+}
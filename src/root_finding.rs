@@ -7,9 +7,9 @@ fn instructions() -> helper::LongPage {
 	helper::LongPage {
 		title: "ROOT-FINDING".to_string(),
 		links:  calculus::links(4),
-		instructions: "In the url bar after <tt>'https://basic-calculus.herokuapp.com/root-finding</tt> type the following:<p align=center>&sol;&lt;point at which to start search for a root&gt;&sol;&lt;function of <i>x</I>&gt;</tt></p>Note that this will not necessarily find the root which is <i>closest</i> to the input point.".to_string(),
+		instructions: "In the url bar after <tt>'https://basic-calculus.herokuapp.com/root-finding</tt> type the following:<p align=center>&sol;&lt;point at which to start search for a root&gt;&sol;&lt;function of <i>x</I>&gt;</tt></p>Instead of a function you may type an equation (&lt;function of <i>x</i>&gt; = &lt;function of <i>x</i>&gt;), in which case the solution of the equation will be found.  Either <tt>=</tt> or <tt>eq</tt> may be used for the equals sign.  Note that this will not necessarily find the root which is <i>closest</i> to the input point.".to_string(),
 		note: format!("{}{}", helper::NOTE1, helper::NOTE2).to_string(),
-		example: "To find a root of the function 2<i>x</i> - 3/(<i>x</i><sup>4</sup> + 5) while starting the search at <i>x</i> = 1, type <tt>/1/2x-3d(x**4+5)</tt> after the current url address.  The result for this should be <tt>0.2995...</tt>".to_string(),
+		example: "To find a root of the function 2<i>x</i> - 3/(<i>x</i><sup>4</sup> + 5) while starting the search at <i>x</i> = 1, type <tt>/1/2x-3d(x**4+5)</tt> after the current url address.  The result for this should be <tt>0.2995...</tt>  To solve the equation cos <i>x</i> = <i>x</i> while starting the search at <i>x</i> = 0, type <tt>/0/cos(x)=x</tt>.  The result for this should be <tt>0.7390...</tt>".to_string(),
 		algorithm: "alternating steps of inverse quadratic interpolation and simple bisection".to_string(),
 		json: "Type '/json' in the url bar immediately after 'root-finding' if you would like the result in this format rather than html.  A successful response will contain five properties. 'xi' is the location where the search starts, 'x' is the root that is eventually found, 'bracket_steps' is the number of steps required to find numbers on either side of (ie, to 'bracket') the root, and 'root_steps' is the subsequent number of steps required for the algorithm to find this root to within the absolute accuracy specified in the last property: 'epsilon'. An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
 	}
@@ -26,54 +26,52 @@ pub struct Results {
 	pub epsilon: f64,
 }
 
-pub fn raw (xi_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
-	let epsilon = (10_f64).powf(-12.);
-	let bracket_steps_max = 30;
-	let xi = match helper::parse_expression(xi_str.to_string()) {
-	  	Ok(x0) => x0,
-	  	Err(message) => return Err(message),
-	};
-	// arbitrary
-	let mut step = 0.1;
-	// First, bracket the root.
-	let mut x0 = xi - step / 2.;
-	let mut x2 = xi + step / 2.;
-	let mut f0 = match helper::function1(input_str.to_string(), x0) {
+// Bracket a root of f, starting from the interval between x0 and x2, which is widened (by a step which grows each time)
+// at whichever end f is smaller.  Returned are the ends of the bracket, f's values there, and the number of steps needed
+// to find it, unless it has not been found after max_steps.
+pub fn bracket<F: Fn(f64) -> Result<f64, String>>(f: &F, x0: f64, x2: f64, step: f64, max_steps: i32) -> Result<Option<(f64, f64, f64, f64, i32)>, String> {
+	let (mut x0, mut x2, mut step) = (x0, x2, step);
+	let mut f0 = match f(x0) {
 		Ok(f0) => f0,
 		Err(message) => return Err(message),
 	};
-	let mut f2 = match helper::function1(input_str.to_string(), x2) {
+	let mut f2 = match f(x2) {
 		Ok(f2) => f2,
 		Err(message) => return Err(message),
 	};
-	let mut bracket_steps = 0;
+	let mut steps = 0;
 	while f0 * f2 > 0. {
 		// golden mean is optimal for this
 		step *= 1.6;
 		if f0.abs() < f2.abs() {
 			x0 -= step;
-			f0 = match helper::function1(input_str.to_string(), x0) {
+			f0 = match f(x0) {
 				Ok(f0) => f0,
 				Err(message) => return Err(message),
 			};
 		} else {
 			x2 += step;
-			f2 = match helper::function1(input_str.to_string(), x2) {
+			f2 = match f(x2) {
 				Ok(f2) => f2,
 				Err(message) => return Err(message),
 			};
 		}
-		bracket_steps += 1;
-		if bracket_steps > bracket_steps_max {
-			return Err(format!("Unable to bracket a root after {} steps.", bracket_steps_max));
+		steps += 1;
+		if steps > max_steps {
+			return Ok(None);
 		}
 	}
-	// Second, find a root that has been bracketed.
-	let root_steps_max = 20;
+	Ok(Some((x0, x2, f0, f2, steps)))
+}
+
+// Find a root of f which has been bracketed by x0 and x2, where f0 and f2 are f's values.
+// Returned are the root and the number of steps needed to find it.
+pub fn bracketed<F: Fn(f64) -> Result<f64, String>>(f: &F, x0: f64, x2: f64, f0: f64, f2: f64, epsilon: f64, root_steps_max: i32) -> Result<(f64, i32), String> {
+	let (mut x0, mut x2, mut f0, mut f2) = (x0, x2, f0, f2);
 	let mut root_steps = 0;
 	// Utilize a third point, to allow inverse-quadratic interpolation.
 	let mut x1 = (x0 + x2) / 2.;
-	let mut f1 = match helper::function1(input_str.to_string(), x1) {
+	let mut f1 = match f(x1) {
 		Ok(f1) => f1,
 		Err(message) => return Err(message),
 	};
@@ -87,7 +85,7 @@ pub fn raw (xi_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
 		if bisect {
 			if f0 * f1 > 0. {
 				let xc = (x1 + x2) / 2.;
-				let fc = match helper::function1(input_str.to_string(), xc) {
+				let fc = match f(xc) {
 					Ok(fc) => fc,
 					Err(message) => return Err(message),
 				};
@@ -100,7 +98,7 @@ pub fn raw (xi_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
 				}
 			} else {
 				let xc = (x1 + x0) / 2.;
-				let fc = match helper::function1(input_str.to_string(), xc) {
+				let fc = match f(xc) {
 					Ok(fc) => fc,
 					Err(message) => return Err(message),
 				};
@@ -121,7 +119,7 @@ pub fn raw (xi_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
 			if xc < x0 || xc > x2 {
 				continue;
 			}
-			let fc = match helper::function1(input_str.to_string(), xc) {
+			let fc = match f(xc) {
 				Ok(fc) => fc,
 				Err(message) => return Err(message),
 			};
@@ -155,6 +153,35 @@ pub fn raw (xi_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
 	} else {
 		x1 = if f2.abs() < f1.abs() {x2} else {x1};
 	}
+	Ok((x1, root_steps))
+}
+
+pub fn raw (xi_str: &RawStr, input_str: &RawStr) -> Result<Results, String> {
+	let epsilon = (10_f64).powf(-12.);
+	let bracket_steps_max = 30;
+	let xi = match helper::parse_expression(xi_str.to_string()) {
+	  	Ok(x0) => x0,
+	  	Err(message) => return Err(message),
+	};
+	// An equation is solved by finding a root of the difference between its two sides.
+	let expression = match helper::equation(input_str, &["x".to_string()]) {
+		Ok(expression) => expression,
+		Err(message) => return Err(message),
+	};
+	let f = |x: f64| helper::function1(expression.clone(), x);
+	// arbitrary
+	let step = 0.1;
+	// First, bracket the root.
+	let (x0, x2, f0, f2, bracket_steps) = match bracket(&f, xi - step / 2., xi + step / 2., step, bracket_steps_max) {
+		Ok(Some(result)) => result,
+		Ok(None) => return Err(format!("Unable to bracket a root after {} steps.", bracket_steps_max)),
+		Err(message) => return Err(message),
+	};
+	// Second, find a root that has been bracketed.
+	let (x1, root_steps) = match bracketed(&f, x0, x2, f0, f2, epsilon, 20) {
+		Ok(result) => result,
+		Err(message) => return Err(message),
+	};
 	Ok(Results {
 		xi,
 		x: x1,
@@ -0,0 +1,194 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+use crate::integrators;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "STOCHASTIC DIFFERENTIAL EQUATIONS".to_string(),
+		links:  calculus::links(20),
+		instructions: format!("This page solves a stochastic differential equation of the form <i>dx</i> = <i>f</i> <i>dt</i> + <i>g</i> <i>dW</i>, where the 'drift' <i>f</i> and the 'diffusion' <i>g</i> are functions of <I>x</I> and <I>t</I> and <i>W</i> is a Wiener process (Brownian motion), ie its changes during non-overlapping intervals of time are independent and normally distributed with mean 0 and variance equal to the interval's length.  The solution starts from a specified value of <I>x</I> when the 'time' <i>t</i> = 0 (by default).  In the url bar after <tt>'https://basic-calculus.herokuapp.com/sde</tt> type the following:<p align=center>&sol;&lt;initial value of <i>x</I>&gt;&sol;&lt;final value of <i>t</I>&gt;&sol;&lt;number of time-steps&gt;&sol;&lt;drift, a function of <i>x</I> and <i>t</I>&gt;&sol;&lt;diffusion, a function of <i>x</I> and <i>t</I>&gt;</tt></p>The initial time may be changed by appending for instance <tt>?ti=1</tt> to the url, but it must precede the final time.  (If more than one option such as this is appended to the url, each after the first must be preceded by <tt>&</tt> rather than <tt>?</tt>.)  Append <tt>?method=milstein</tt> to use the Milstein method rather than the default (<tt>euler-maruyama</tt>).  The random numbers are reproducible: the same url always gives the same result, unless a different nonnegative integer 'seed' is chosen by appending for instance <tt>&seed=7</tt>.  To solve the equation for an ensemble of independent paths (at most {}) and obtain their mean and variance rather than a single path, append for instance <tt>&paths=1000</tt>.", MAX_PATHS),
+		note: format!("{}{}", helper::NOTE1, helper::NOTE2).to_string(),
+		example: "For Brownian motion (f = 0 and g = 1) from t = 0 to t = 1 using 100 time-steps, starting from x = 0, type <tt>/0/1/100/0/1?paths=1000</tt> after /sde in the url above.  The mean and variance of x(1) should be close to 0 and 1, respectively.  For the geometric Brownian motion of a stock price with a growth rate of 5% and a volatility of 20%, typing <tt>/1/1/100/0.05x/0.2x?paths=1000</tt> should give a mean and variance of x(1) close to exp(0.05) = 1.051... and exp(0.1)(exp(0.04) - 1) = 0.0451..., respectively.  Removing <tt>?paths=1000</tt> gives one possible path of the price.".to_string(),
+		algorithm: "The time-steps are of equal size <i>dt</i>.  For each of them a change <i>dW</i> of the Wiener process is drawn from a normal distribution with mean 0 and variance <i>dt</i>, using the Box-Muller transformation of uniformly distributed random numbers, which are generated by the 'SplitMix64' algorithm from the seed.  Each step of the <tt>euler-maruyama</tt> method changes <i>x</i> by <i>f</i> <i>dt</i> + <i>g</i> <i>dW</i>, evaluated at the start of the step; the errors of its paths are of order <i>dt</i><sup>1/2</sup>.  The <tt>milstein</tt> method adds <i>g</i> (<i>dg/dx</i>) (<i>dW</i><sup>2</sup> - <i>dt</i>)/2, which reduces these errors to order <i>dt</i>.  The derivative <i>dg/dx</i> is calculated by a central difference.  The variance of an ensemble is the unbiased estimate (in which the sum of the squared deviations from the mean is divided by one less than the number of paths).".to_string(),
+		json: "Type '/json' in the url bar immediately after 'sde' if you would like the result in this format rather than html.  A successful response will contain eleven properties. 'ti' is the initial time, 'xi' is the initial value, 'tf' and 'nt' are the final time and number of time-steps, 'method' is the integration method, 'seed' is the seed of the random numbers, 'paths' is the number of paths, and 'ts' is the array of output times.  For a single path 'xs' is the array of the values of <i>x</i> at those times, and 'means' and 'variances' are null.  For an ensemble 'xs' is null, and 'means' and 'variances' are arrays of the ensemble's mean and variance at those times. An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+const MAX_PATHS: i32 = 10000;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub ti: f64,
+	pub xi: f64,
+	pub tf: f64,
+	pub nt: i32,
+	pub method: String,
+	pub seed: u64,
+	pub paths: i32,
+	pub ts: Vec<f64>,
+	pub xs: Option<Vec<f64>>,
+	pub means: Option<Vec<f64>>,
+	pub variances: Option<Vec<f64>>,
+}
+
+// SplitMix64 generator of uniformly distributed random numbers, with normally distributed ones obtained in pairs by the Box-Muller transformation
+struct Random {
+	state: u64,
+	spare: Option<f64>,
+}
+
+impl Random {
+	fn new(seed: u64) -> Random {
+		Random {state: seed, spare: None}
+	}
+	fn next(&mut self) -> u64 {
+		self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+		let mut z = self.state;
+		z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+		z ^ (z >> 31)
+	}
+	// uniformly distributed in (0, 1], so that its logarithm is finite
+	fn uniform(&mut self) -> f64 {
+		((self.next() >> 11) + 1) as f64 / (1u64 << 53) as f64
+	}
+	// normally distributed with mean 0 and variance 1
+	fn normal(&mut self) -> f64 {
+		if let Some(spare) = self.spare.take() {
+			return spare;
+		}
+		let radius = (-2. * self.uniform().ln()).sqrt();
+		let angle = 2. * std::f64::consts::PI * self.uniform();
+		self.spare = Some(radius * angle.sin());
+		radius * angle.cos()
+	}
+}
+
+pub fn raw(
+	xi_str: &RawStr,
+	tf_str: &RawStr,
+	nt_str: &RawStr,
+	drift_str: &RawStr,
+	diffusion_str: &RawStr,
+	ti_str: Option<&str>,
+	method: Option<&str>,
+	seed_str: Option<&str>,
+	paths_str: Option<&str>,
+) -> Result<Results, String> {
+	let methods = ["euler-maruyama", "milstein"];
+	let method = method.unwrap_or("euler-maruyama").to_lowercase();
+	if !methods.contains(&method.as_str()) {
+		return Err(format!("Error: the method must be one of {}, not '{}'.", methods.join(", "), method));
+	}
+	// The seed is parsed exactly, since any nonnegative 64-bit integer is allowed.
+	let seed = match seed_str {
+		Some(seed_str) => match seed_str.parse::<u64>() {
+			Ok(seed) => seed,
+			Err(_) => return Err(format!("Error: the seed must be a nonnegative integer less than 2^64, unlike {}.", seed_str)),
+		},
+		None => 0,
+	};
+	let paths = match helper::parse_count(paths_str, 1, "number of paths") {
+		Ok(paths) => {
+			if paths > MAX_PATHS as usize {
+				return Err(format!("Error: the number of paths may not exceed {}.", MAX_PATHS));
+			}
+			paths as i32
+		},
+		Err(message) => return Err(message),
+	};
+	let ti = match integrators::initial_time(ti_str) {
+		Ok(ti) => ti,
+		Err(message) => return Err(message),
+	};
+	let xi = match helper::parse_expression(xi_str.to_string()) {
+		Ok(xi) => xi,
+		Err(message) => return Err(message),
+	};
+	let tf = match helper::parse_expression(tf_str.to_string()) {
+		Ok(tf) => tf,
+		Err(message) => return Err(message),
+	};
+	if tf <= ti {
+		return Err(format!("Error: the final time ({}) must exceed the initial time ({}).", tf, ti));
+	}
+	let nt = match helper::parse_expression(nt_str.to_string()) {
+		Ok(nt) => {
+			if nt.round() != nt {
+				return Err(format!("{} is not an integer.", nt));
+			} else if nt <= 0. {
+				return Err("Number of timesteps must be positive.".to_string());
+			}
+			nt as i32
+		},
+		Err(message) => return Err(message),
+	};
+	let names: Vec<String> = ["x", "t"].iter().map(|name| name.to_string()).collect();
+	let drift = |x: f64, t: f64| helper::function_n(drift_str, &names, &[x, t]);
+	let diffusion = |x: f64, t: f64| helper::function_n(diffusion_str, &names, &[x, t]);
+	let milstein = method == "milstein";
+	let ts: Vec<f64> = (0..=nt).map(|i| if i == nt {tf} else {ti + (i as f64) * (tf - ti) / (nt as f64)}).collect();
+	let mut random = Random::new(seed);
+	let mut xs = vec![];
+	// running means and sums of squared deviations from them (Welford's algorithm)
+	let mut means = vec![0.; ts.len()];
+	let mut squares = vec![0.; ts.len()];
+	for path in 0..paths {
+		let mut x = xi;
+		let mut path_xs = vec![xi];
+		for i in 1..ts.len() {
+			let t = ts[i - 1];
+			let dt = ts[i] - t;
+			let dw = dt.sqrt() * random.normal();
+			let f = match drift(x, t) {
+				Ok(f) => f,
+				Err(message) => return Err(message),
+			};
+			let g = match diffusion(x, t) {
+				Ok(g) => g,
+				Err(message) => return Err(message),
+			};
+			let mut dx = f * dt + g * dw;
+			if milstein {
+				let h = 1e-5 * x.abs().max(1.);
+				let dg = match (diffusion(x + h, t), diffusion(x - h, t)) {
+					(Ok(g_plus), Ok(g_minus)) => (g_plus - g_minus) / (2. * h),
+					(Err(message), _) | (_, Err(message)) => return Err(message),
+				};
+				dx += g * dg * (dw * dw - dt) / 2.;
+			}
+			x += dx;
+			if !x.is_finite() {
+				return Err(format!("Error: the solution diverges at t = {}.", ts[i]));
+			}
+			path_xs.push(x);
+		}
+		let n = (path + 1) as f64;
+		for (i, x) in path_xs.iter().enumerate() {
+			let deviation = x - means[i];
+			means[i] += deviation / n;
+			squares[i] += deviation * (x - means[i]);
+		}
+		if paths == 1 {
+			xs = path_xs;
+		}
+	}
+	let ensemble = paths > 1;
+	Ok(Results {
+		ti,
+		xi,
+		tf,
+		nt,
+		method,
+		seed,
+		paths,
+		ts,
+		xs: if ensemble {None} else {Some(xs)},
+		means: if ensemble {Some(means)} else {None},
+		variances: if ensemble {Some(squares.iter().map(|square| square / ((paths - 1) as f64)).collect())} else {None},
+	})
+}
@@ -0,0 +1,183 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+use std::f64::consts::PI;
+
+use crate::helper;
+use crate::integrators;
+use crate::root_finding;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "STURM-LIOUVILLE EIGENVALUE PROBLEMS".to_string(),
+		links:  calculus::links(17),
+		instructions: "This page finds the lowest eigenvalues &lambda; and the corresponding eigenfunctions <i>y</i>(<i>x</i>) of the equation -<i>d</i><sup>2</sup><i>y/dx</i><sup>2</sup> + <i>q</i>(<i>x</i>) <i>y</i> = &lambda; <i>w</i>(<i>x</i>) <i>y</i> over an interval, at each end of which either <i>y</i> vanishes (a 'Dirichlet' condition) or <i>dy/dx</i> does (a 'Neumann' condition).  In the url bar after <tt>'https://basic-calculus.herokuapp.com/sturm-liouville</tt> type the following:<p align=center>&sol;&lt;lower end of the interval&gt;&sol;&lt;upper end of the interval&gt;&sol;&lt;number of eigenvalues&gt;&sol;&lt;function <i>q</i> of <i>x</I>&gt;</tt></p>By default the weight <i>w</i> equals 1, and the boundary conditions are Dirichlet at both ends.  A different (positive) weight may be specified by appending for instance <tt>?w=1+x**2</tt> to the url, and Neumann conditions may be specified by appending <tt>left=neumann</tt> and/or <tt>right=neumann</tt>.  Each eigenfunction is sampled at 100 evenly spaced intervals by default, a number which may be changed by appending for instance <tt>nx=200</tt>.  (If more than one option such as these is appended to the url, each after the first must be preceded by <tt>&</tt> rather than <tt>?</tt>.)".to_string(),
+		note: format!("{}{}", helper::NOTE1, helper::NOTE2).to_string(),
+		example: "For a quantum particle in a harmonic well (in units in which the eigenvalues should be 1, 3, 5, ...), type <tt>/-6/6/3/x**2</tt> after the current url address.  The eigenvalues should be <tt>1, 3, and 5</tt> (to within 10<sup>-8</sup> or so), and the first eigenfunction should be proportional to exp(-<i>x</i><sup>2</sup>/2).  For a vibrating string with fixed ends, type <tt>/0/1/3/0</tt>, for which the eigenvalues should be (<i>n</i>&pi;)<sup>2</sup> = <tt>9.869..., 39.47..., and 88.82...</tt>.".to_string(),
+		algorithm: "Writing <i>y</i> = <i>r</i> sin &theta; and <i>dy/dx</i> = <i>r</i> cos &theta; gives a 1st-order differential equation for the 'Prüfer angle' &theta;, which is integrated by the rk45 method of the 1st-order ODE page from each end of the interval to the bottom of the 'well' (ie where <i>q/w</i> is smallest), starting from the angles which satisfy the boundary conditions.  (Integrating toward the well avoids the growth of errors in the regions where the eigenfunctions decay exponentially.)  The difference between the two angles at this point increases with &lambda;, and the <i>n</i>-th eigenvalue is the value of &lambda; for which this difference equals (<i>n</i> - 1)&pi; (ie for which the eigenfunction has <i>n</i> - 1 zeros inside the interval).  This value is bracketed and then found as on the root-finding page.  Each eigenfunction is then found by integrating the original equation from each end and matching the two solutions, and it is normalized so that the integral of <i>w</i> <i>y</i><sup>2</sup> over the interval equals 1 and so that it is positive near the lower end.".to_string(),
+		json: "Type '/json' in the url bar immediately after 'sturm-liouville' if you would like the result in this format rather than html.  A successful response will contain eight properties. 'a' and 'b' are the ends of the interval, 'left' and 'right' are the boundary conditions there (either 'dirichlet' or 'neumann'), 'eigenvalues' is an array of the eigenvalues (in increasing order), 'xs' is the array of points at which the eigenfunctions are sampled, 'eigenfunctions' is an array of the corresponding arrays of the eigenfunctions' values, and 'epsilon' is the accuracy to which the eigenvalues' boundary conditions are satisfied. An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub a: f64,
+	pub b: f64,
+	pub left: String,
+	pub right: String,
+	pub eigenvalues: Vec<f64>,
+	pub xs: Vec<f64>,
+	pub eigenfunctions: Vec<Vec<f64>>,
+	pub epsilon: f64,
+}
+
+// Prüfer angle at which a boundary condition holds, to within a multiple of pi
+fn angle(condition: Option<&str>) -> Result<(String, f64), String> {
+	let condition = condition.unwrap_or("dirichlet").to_lowercase();
+	match condition.as_str() {
+		"dirichlet" => Ok((condition, 0.)),
+		"neumann" => Ok((condition, PI / 2.)),
+		_ => Err(format!("Error: each boundary condition must be either 'dirichlet' or 'neumann', not '{}'.", condition)),
+	}
+}
+
+pub fn raw(
+	a_str: &RawStr,
+	b_str: &RawStr,
+	n_str: &RawStr,
+	q_str: &RawStr,
+	w_str: Option<&str>,
+	left: Option<&str>,
+	right: Option<&str>,
+	nx_str: Option<&str>,
+) -> Result<Results, String> {
+	let epsilon = (10_f64).powf(-10.);
+	let bracket_steps_max = 100;
+	let a = match helper::parse_expression(a_str.to_string()) {
+		Ok(a) => a,
+		Err(message) => return Err(message),
+	};
+	let b = match helper::parse_expression(b_str.to_string()) {
+		Ok(b) => b,
+		Err(message) => return Err(message),
+	};
+	if b <= a {
+		return Err(format!("Error: the upper end of the interval ({}) must exceed its lower end ({}).", b, a));
+	}
+	let n = match helper::parse_expression(n_str.to_string()) {
+		Ok(n) => {
+			if n.round() != n || n <= 0. {
+				return Err(format!("Error: the number of eigenvalues must be a positive integer, unlike {}.", n));
+			}
+			n as usize
+		},
+		Err(message) => return Err(message),
+	};
+	let nx = match nx_str {
+		Some(nx_str) => match helper::parse_expression(nx_str.to_string()) {
+			Ok(nx) => {
+				if nx.round() != nx || nx <= 0. {
+					return Err(format!("Error: the number of intervals must be a positive integer, unlike {}.", nx));
+				}
+				nx as i32
+			},
+			Err(message) => return Err(message),
+		},
+		None => 100,
+	};
+	let (left, alpha) = match angle(left) {
+		Ok(result) => result,
+		Err(message) => return Err(message),
+	};
+	// The angle at the upper end is taken to lie in (0, pi], so that it exceeds the initial angle.
+	let (right, beta) = match angle(right) {
+		Ok((right, beta)) => (right, if beta == 0. {PI} else {beta}),
+		Err(message) => return Err(message),
+	};
+	let w_expression = w_str.unwrap_or("1").to_string();
+	let q = |x: f64| helper::function1(q_str.to_string(), x);
+	let w = |x: f64| helper::function1(w_expression.clone(), x);
+	let xs: Vec<f64> = (0..=nx).map(|i| if i == nx {b} else {a + (i as f64) * (b - a) / (nx as f64)}).collect();
+	// The two solutions (from each end) are matched at the bottom of the 'well', ie where q/w is smallest.
+	let mut ic = 0;
+	let mut well = f64::INFINITY;
+	for (i, x) in xs.iter().enumerate() {
+		let (q, w) = match (q(*x), w(*x)) {
+			(Ok(q), Ok(w)) => (q, w),
+			(Err(message), _) | (_, Err(message)) => return Err(message),
+		};
+		if w <= 0. {
+			return Err(format!("Error: the weight must be positive, unlike its value {} at x = {}.", w, x));
+		}
+		if q / w < well {
+			well = q / w;
+			ic = i;
+		}
+	}
+	let c = xs[ic];
+	let settings = match integrators::settings(Some("rk45"), Some("1e-10"), Some("1e-12")) {
+		Ok(settings) => settings,
+		Err(message) => return Err(message),
+	};
+	let none = integrators::Events::none();
+	// Integrate from one end of the interval to the matching point, in nt steps.
+	let shoot = |f: &dyn Fn(f64, &[f64]) -> Result<Vec<f64>, String>, x0: f64, y0: &[f64], nt: usize| -> Result<Vec<Vec<f64>>, String> {
+		if nt == 0 {
+			return Ok(vec![y0.to_vec()]);
+		}
+		match integrators::solve(&f, x0, y0, c, nt as i32, &settings, &none) {
+			Ok(solution) => Ok(solution.ys),
+			Err(message) => Err(message),
+		}
+	};
+	// difference between the Prüfer angles of the solutions from each end, at the matching point
+	let mismatch = |lambda: f64| -> Result<f64, String> {
+		let f = |x: f64, y: &[f64]| match (q(x), w(x)) {
+			(Ok(q), Ok(w)) => Ok(vec![y[0].cos().powi(2) + (lambda * w - q) * y[0].sin().powi(2)]),
+			(Err(message), _) | (_, Err(message)) => Err(message),
+		};
+		match (shoot(&f, a, &[alpha], 1.min(ic)), shoot(&f, b, &[beta], 1.min(nx as usize - ic))) {
+			(Ok(from_a), Ok(from_b)) => Ok(from_a[from_a.len() - 1][0] - from_b[from_b.len() - 1][0]),
+			(Err(message), _) | (_, Err(message)) => Err(message),
+		}
+	};
+	let mut eigenvalues = vec![];
+	let mut eigenfunctions = vec![];
+	for k in 0..n {
+		let g = |lambda: f64| mismatch(lambda).map(|mismatch| mismatch - (k as f64) * PI);
+		// First, bracket the eigenvalue, starting from that of a uniform string (or from the previous eigenvalue).
+		let guess = well + (((k + 1) as f64) * PI / (b - a)).powi(2) / w(c).unwrap_or(1.);
+		// Since g increases with lambda, the bracket widens toward the eigenvalue.
+		let start = if k == 0 {guess} else {eigenvalues[k - 1]};
+		let (lambda0, lambda2, g0, g2, _) = match root_finding::bracket(&g, start, start, 0.1 * guess.abs().max(1.), bracket_steps_max) {
+			Ok(Some(result)) => result,
+			Ok(None) => return Err(format!("Unable to bracket eigenvalue number {} after {} steps.", k + 1, bracket_steps_max)),
+			Err(message) => return Err(message),
+		};
+		// Second, find the eigenvalue that has been bracketed.
+		let (lambda, _) = match root_finding::bracketed(&g, lambda0, lambda2, g0, g2, epsilon, 100) {
+			Ok(result) => result,
+			Err(message) => return Err(message),
+		};
+		// Integrate y, dy/dx, and the integral of w y^2 from each end, and then match the two solutions.
+		let f = |x: f64, y: &[f64]| match (q(x), w(x)) {
+			(Ok(q), Ok(w)) => Ok(vec![y[1], (q - lambda * w) * y[0], w * y[0] * y[0]]),
+			(Err(message), _) | (_, Err(message)) => Err(message),
+		};
+		let (from_a, from_b) = match (shoot(&f, a, &[alpha.sin(), alpha.cos(), 0.], ic), shoot(&f, b, &[beta.sin(), beta.cos(), 0.], nx as usize - ic)) {
+			(Ok(from_a), Ok(from_b)) => (from_a, from_b),
+			(Err(message), _) | (_, Err(message)) => return Err(message),
+		};
+		let (ya, yb) = (&from_a[ic], &from_b[nx as usize - ic]);
+		// The two solutions are proportional, with a ratio which is best found from the larger of y and dy/dx.
+		let ratio = if ya[0].abs() > ya[1].abs() {ya[0] / yb[0]} else {ya[1] / yb[1]};
+		// The right-hand integral of w y^2 was accumulated backward.
+		let norm = (ya[2] - ratio * ratio * yb[2]).sqrt();
+		let mut eigenfunction: Vec<f64> = from_a.iter().map(|y| y[0] / norm).collect();
+		eigenfunction.extend(from_b.iter().rev().skip(1).map(|y| ratio * y[0] / norm));
+		eigenvalues.push(lambda);
+		eigenfunctions.push(eigenfunction);
+	}
+	Ok(Results {a, b, left, right, eigenvalues, xs, eigenfunctions, epsilon})
+}
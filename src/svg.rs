@@ -0,0 +1,261 @@
+// SVG images of the results of the pages
+
+use crate::helper;
+
+const WIDTH: f64 = 400.;
+const HEIGHT: f64 = 300.;
+// space around the plotting area, for the axes' labels
+const MARGIN: f64 = 50.;
+// colors of successive curves
+const COLORS: [&str; 6] = ["blue", "red", "green", "orange", "purple", "brown"];
+
+pub fn color(i: usize) -> &'static str {COLORS[i % COLORS.len()]}
+
+// rectangular window of the plane, which is mapped onto the plotting area
+pub struct Frame {
+	pub left: f64,
+	pub right: f64,
+	pub bottom: f64,
+	pub top: f64,
+}
+
+impl Frame {
+	// the smallest window which contains all of the (finite) points, widened slightly vertically
+	pub fn fit(points: &[[f64; 2]]) -> Frame {
+		let mut frame = Frame {left: f64::INFINITY, right: f64::NEG_INFINITY, bottom: f64::INFINITY, top: f64::NEG_INFINITY};
+		for point in points.iter().filter(|point| point[0].is_finite() && point[1].is_finite()) {
+			frame.left = frame.left.min(point[0]);
+			frame.right = frame.right.max(point[0]);
+			frame.bottom = frame.bottom.min(point[1]);
+			frame.top = frame.top.max(point[1]);
+		}
+		// A window must have a positive width and height, even if there are no points or all of them have the same coordinate.
+		if frame.left > frame.right {
+			frame = Frame {left: -1., right: 1., bottom: -1., top: 1.};
+		}
+		if frame.right - frame.left <= 1e-12 * frame.left.abs() {
+			frame.left -= 0.5 * frame.left.abs().max(1.);
+			frame.right += 0.5 * frame.right.abs().max(1.);
+		}
+		if frame.top - frame.bottom <= 1e-12 * frame.bottom.abs() {
+			frame.bottom -= 0.5 * frame.bottom.abs().max(1.);
+			frame.top += 0.5 * frame.top.abs().max(1.);
+		}
+		let padding = 0.05 * (frame.top - frame.bottom);
+		frame.bottom -= padding;
+		frame.top += padding;
+		frame
+	}
+	// screen coordinates of a point
+	pub fn x(&self, x: f64) -> f64 {MARGIN + WIDTH * (x - self.left) / (self.right - self.left)}
+	pub fn y(&self, y: f64) -> f64 {MARGIN + HEIGHT * (self.top - y) / (self.top - self.bottom)}
+	// screen lengths of the units of the two coordinates
+	pub fn scales(&self) -> (f64, f64) {(WIDTH / (self.right - self.left), HEIGHT / (self.top - self.bottom))}
+}
+
+// how a series of points is drawn
+pub enum Style {
+	Line,
+	Dots,
+	// the region between the curve and the horizontal axis is shaded
+	Area,
+}
+
+pub struct Series {
+	pub label: String,
+	pub points: Vec<[f64; 2]>,
+	pub style: Style,
+}
+
+// points on the graph of a function of x, evaluated at evenly spaced values of x (omitting those where it does not exist)
+pub fn graph(expression: &str, left: f64, right: f64) -> Vec<[f64; 2]> {
+	let n = 200;
+	let mut points = vec![];
+	for i in 0..=n {
+		let x = left + (right - left) * (i as f64) / (n as f64);
+		if let Ok(y) = helper::function1(expression.to_string(), x) {
+			if y.is_finite() {
+				points.push([x, y]);
+			}
+		}
+	}
+	points
+}
+
+// curve through the points, each of which is (horizontal, vertical)
+pub fn polyline(frame: &Frame, points: &[[f64; 2]], color: &str) -> String {
+	let coordinates: Vec<String> = points.iter()
+		.filter(|point| point[0].is_finite() && point[1].is_finite())
+		.map(|point| format!("{:.2},{:.2}", frame.x(point[0]), frame.y(point[1])))
+		.collect();
+	format!("<polyline points='{}' fill='none' stroke='{}' stroke-width='1.5'/>", coordinates.join(" "), color)
+}
+
+fn dots(frame: &Frame, points: &[[f64; 2]], color: &str) -> String {
+	points.iter()
+		.filter(|point| point[0].is_finite() && point[1].is_finite())
+		.map(|point| format!("<circle cx='{:.2}' cy='{:.2}' r='2.5' fill='{}'/>", frame.x(point[0]), frame.y(point[1]), color))
+		.collect()
+}
+
+// region between the curve through the points and the horizontal axis
+fn area(frame: &Frame, points: &[[f64; 2]], color: &str) -> String {
+	let points: Vec<&[f64; 2]> = points.iter().filter(|point| point[0].is_finite() && point[1].is_finite()).collect();
+	if points.is_empty() {
+		return "".to_string();
+	}
+	let mut coordinates = vec![format!("{:.2},{:.2}", frame.x(points[0][0]), frame.y(0.))];
+	for point in &points {
+		coordinates.push(format!("{:.2},{:.2}", frame.x(point[0]), frame.y(point[1])));
+	}
+	coordinates.push(format!("{:.2},{:.2}", frame.x(points[points.len() - 1][0]), frame.y(0.)));
+	format!("<polygon points='{}' fill='{}' fill-opacity='0.3' stroke='none'/>", coordinates.join(" "), color)
+}
+
+// segment of the given screen length, centered at (x, y) and parallel to (dx, dy), with an arrowhead if requested
+pub fn arrow(frame: &Frame, x: f64, y: f64, dx: f64, dy: f64, length: f64, head: bool) -> String {
+	let (sx, sy) = frame.scales();
+	let (ex, ey) = (dx * sx, -dy * sy);
+	let norm = ex.hypot(ey);
+	let (cx, cy) = (frame.x(x), frame.y(y));
+	if !norm.is_finite() {
+		return "".to_string();
+	}
+	if norm == 0. {
+		return format!("<circle cx='{:.2}' cy='{:.2}' r='1' fill='gray'/>", cx, cy);
+	}
+	let (ux, uy) = (ex / norm * length / 2., ey / norm * length / 2.);
+	format!(
+		"<line x1='{:.2}' y1='{:.2}' x2='{:.2}' y2='{:.2}' stroke='gray'{}/>",
+		cx - ux, cy - uy, cx + ux, cy + uy,
+		if head {" marker-end='url(#head)'"} else {""},
+	)
+}
+
+// round values (multiples of 1, 2, or 5 times a power of 10) which divide the range into 4 to 10 parts, and the number of decimals which they need
+fn ticks(min: f64, max: f64) -> (Vec<f64>, usize) {
+	let rough = (max - min) / 5.;
+	let power = (10_f64).powf(rough.log10().floor());
+	let step = [1., 2., 5., 10.].iter().map(|factor| factor * power).find(|step| *step >= rough).unwrap_or(10. * power);
+	let decimals = (-step.log10().floor()).max(0.) as usize;
+	let first = (min / step).ceil() as i64;
+	let last = (max / step).floor() as i64;
+	((first..=last).map(|k| (k as f64) * step).collect(), decimals)
+}
+
+// image of the content, clipped to the frame, with ticks along the frame, the axes (if they are within it), a legend, and the axes' labels
+pub fn image(frame: &Frame, content: &str, legend: &[(String, &str)], horizontal: &str, vertical: &str) -> String {
+	let mut labels = "".to_string();
+	let (xs, x_decimals) = ticks(frame.left, frame.right);
+	for x in xs {
+		let sx = frame.x(x);
+		labels = format!(
+			"{}<line x1='{:.2}' y1='{}' x2='{:.2}' y2='{}' stroke='black'/><text x='{:.2}' y='{}' text-anchor='middle'>{:.*}</text>",
+			labels, sx, MARGIN + HEIGHT, sx, MARGIN + HEIGHT + 5., sx, MARGIN + HEIGHT + 18., x_decimals, x + 0.,
+		);
+	}
+	let (ys, y_decimals) = ticks(frame.bottom, frame.top);
+	for y in ys {
+		let sy = frame.y(y);
+		labels = format!(
+			"{}<line x1='{}' y1='{:.2}' x2='{}' y2='{:.2}' stroke='black'/><text x='{}' y='{:.2}' text-anchor='end'>{:.*}</text>",
+			labels, MARGIN - 5., sy, MARGIN, sy, MARGIN - 7., sy + 4., y_decimals, y + 0.,
+		);
+	}
+	let mut axes = "".to_string();
+	if frame.left < 0. && frame.right > 0. {
+		axes = format!("<line x1='{:.2}' y1='{}' x2='{:.2}' y2='{}' stroke='lightgray'/>", frame.x(0.), MARGIN, frame.x(0.), MARGIN + HEIGHT);
+	}
+	if frame.bottom < 0. && frame.top > 0. {
+		axes = format!("{}<line x1='{}' y1='{:.2}' x2='{}' y2='{:.2}' stroke='lightgray'/>", axes, MARGIN, frame.y(0.), MARGIN + WIDTH, frame.y(0.));
+	}
+	let mut key = "".to_string();
+	for (k, (label, color)) in legend.iter().enumerate() {
+		key = format!("{}<text x='{}' y='{}' fill='{}'>{}</text>", key, MARGIN + (k as f64) * WIDTH / (legend.len() as f64), MARGIN - 15., color, label);
+	}
+	format!(
+		"<svg width='{}' height='{}' font-size='12'>
+		<defs>
+			<clipPath id='area'><rect x='{}' y='{}' width='{}' height='{}'/></clipPath>
+			<marker id='head' markerWidth='6' markerHeight='6' refX='5' refY='3' orient='auto'><path d='M0,0 L6,3 L0,6 z' fill='gray'/></marker>
+		</defs>
+		{}
+		<g clip-path='url(#area)'>{}</g>
+		<rect x='{}' y='{}' width='{}' height='{}' fill='none' stroke='black'/>
+		{}{}
+		<text x='{}' y='{}' text-anchor='middle' font-style='italic'>{}</text>
+		<text x='{}' y='{}' text-anchor='middle' font-style='italic'>{}</text>
+		</svg>",
+		WIDTH + 2. * MARGIN, HEIGHT + 2. * MARGIN,
+		MARGIN, MARGIN, WIDTH, HEIGHT,
+		axes,
+		content,
+		MARGIN, MARGIN, WIDTH, HEIGHT,
+		labels, key,
+		MARGIN + WIDTH / 2., MARGIN + HEIGHT + 35., horizontal,
+		MARGIN / 4., MARGIN + HEIGHT / 2., vertical,
+	)
+}
+
+// plot of the series, in a window which contains them all
+pub fn plot(series: &[Series], horizontal: &str, vertical: &str) -> String {
+	let mut points: Vec<[f64; 2]> = series.iter().flat_map(|series| series.points.clone()).collect();
+	// A shaded area extends to the horizontal axis.
+	for series in series.iter().filter(|series| matches!(series.style, Style::Area)) {
+		points.extend(series.points.iter().map(|point| [point[0], 0.]));
+	}
+	let frame = Frame::fit(&points);
+	let mut content = "".to_string();
+	let mut legend = vec![];
+	for (k, series) in series.iter().enumerate() {
+		content = format!("{}{}", content, match series.style {
+			Style::Line => polyline(&frame, &series.points, color(k)),
+			Style::Dots => dots(&frame, &series.points, color(k)),
+			Style::Area => area(&frame, &series.points, color(k)),
+		});
+		legend.push((series.label.clone(), color(k)));
+	}
+	format!("<div style='display: flex; justify-content: center;'>{}</div>", image(&frame, &content, &legend, horizontal, vertical))
+}
+
+// short label of a bound
+fn number(x: f64) -> String {
+	let rounded = format!("{:.4}", x);
+	rounded.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+// image in which each value of u(x, t) is a colored rectangle, from blue for the smallest to red for the largest
+pub fn heat_map(xs: &[f64], ts: &[f64], us: &[Vec<f64>]) -> String {
+	let min = us.iter().flatten().fold(f64::INFINITY, |min, u| min.min(*u));
+	let max = us.iter().flatten().fold(f64::NEG_INFINITY, |max, u| max.max(*u));
+	let (dx, dt) = (WIDTH / (xs.len() as f64), HEIGHT / (ts.len() as f64));
+	let mut rects = "".to_string();
+	for (i, row) in us.iter().enumerate() {
+		for (j, u) in row.iter().enumerate() {
+			let fraction = if max > min {(u - min) / (max - min)} else {0.5};
+			rects = format!(
+				"{}<rect x='{:.2}' y='{:.2}' width='{:.2}' height='{:.2}' fill='hsl({:.0}, 100%, 50%)'><title>u({}, {}) = {}</title></rect>",
+				rects, MARGIN + (j as f64) * dx, MARGIN + (i as f64) * dt, dx, dt, 240. * (1. - fraction), xs[j], ts[i], u,
+			);
+		}
+	}
+	format!(
+		"<svg width='{}' height='{}' font-size='12' shape-rendering='crispEdges'>{}
+		<text x='{}' y='{}' text-anchor='start'>{}</text>
+		<text x='{}' y='{}' text-anchor='end'>{}</text>
+		<text x='{}' y='{}' text-anchor='middle' font-style='italic'>x</text>
+		<text x='{}' y='{}' text-anchor='end'>{}</text>
+		<text x='{}' y='{}' text-anchor='end'>{}</text>
+		<text x='{}' y='{}' text-anchor='middle' font-style='italic'>t</text>
+		</svg>
+		<br>The color varies from blue (<i>u</i> = {}) to red (<i>u</i> = {}).",
+		WIDTH + 2. * MARGIN, HEIGHT + 2. * MARGIN, rects,
+		MARGIN, MARGIN - 5., number(xs[0]),
+		MARGIN + WIDTH, MARGIN - 5., number(xs[xs.len() - 1]),
+		MARGIN + WIDTH / 2., MARGIN - 20.,
+		MARGIN - 5., MARGIN + 10., number(ts[0]),
+		MARGIN - 5., MARGIN + HEIGHT, number(ts[ts.len() - 1]),
+		MARGIN / 2., MARGIN + HEIGHT / 2.,
+		min, max,
+	)
+}
@@ -0,0 +1,108 @@
+use rocket::http::RawStr;
+use serde::{Serialize, Deserialize};
+
+use crate::helper;
+
+fn instructions() -> helper::LongPage {
+	helper::LongPage {
+		title: "TABLES OF FUNCTION VALUES".to_string(),
+		links:  calculus::links(23),
+		instructions: format!("This page evaluates a function of <i>x</i> at evenly spaced points between (and including) two values of <i>x</i>.  In the url bar after <tt>'https://basic-calculus.herokuapp.com/table</tt> type the following:<p align=center>&sol;&lt;first value of <i>x</I>&gt;&sol;&lt;last value of <i>x</I>&gt;&sol;&lt;number of points&gt;&sol;&lt;function of <i>x</I>&gt;</tt></p>The last value must exceed the first, and the number of points must be between 2 and {}.  To space the points evenly on a logarithmic scale (ie so that each value of <i>x</i> is the same multiple of the previous one) rather than a linear one, append <tt>?spacing=log</tt> to the url, in which case both values of <i>x</i> must be positive.  A point at which the function cannot be evaluated (for instance the square root of a negative number) does not spoil the table, which instead reports the error for that point.", MAX_POINTS),
+		note: format!("{}{}", helper::NOTE1, helper::NOTE2).to_string(),
+		example: "To tabulate the function sqrt(<i>x</i>) at the 5 points <i>x</i> = -1, 0, 1, 2, and 3, type <tt>/-1/3/5/sqrt(x)</tt> after /table in the url above.  The first point should report an error, and the others should give 0, 1, 1.414..., and 1.732....  For the function ln(<i>x</i>) at <i>x</i> = 1, 10, 100, and 1000, typing <tt>/1/1000/4/ln(x)?spacing=log</tt> should give 0, 2.302..., 4.605..., and 6.907....".to_string(),
+		algorithm: "For linear spacing the <i>i</i>-th of the <i>n</i> points (counting from 0) is <i>a</i> + <i>i</i> (<i>b</i> - <i>a</i>)/(<i>n</i> - 1), where <i>a</i> and <i>b</i> are the first and last values of <i>x</i>.  For logarithmic spacing it is <i>a</i> (<i>b</i>/<i>a</i>)<sup><i>i</i>/(<i>n</i> - 1)</sup>.  In either case the last point is exactly <i>b</i>.  A value of the function which is infinite or not a number is reported as an error.".to_string(),
+		json: "Type '/json' in the url bar immediately after 'table' if you would like the result in this format rather than html, or '/csv' if you would like it as comma-separated values (one line for each point, after a line with the headings 'x', 'f', and 'error').  A successful JSON response will contain five properties. 'a' and 'b' are the first and last values of <i>x</i>, 'n' is the number of points, 'spacing' is either 'linear' or 'log', and 'points' is the array of points, each of which has the properties 'x', 'f' (the value of the function, or null if it could not be evaluated), and 'error' (null, or a string reporting why the function could not be evaluated). An unsuccessful response will have one property: 'message' (a string reporting the error).".to_string(),
+	}
+}
+
+pub fn page() -> String {helper::format(instructions())}
+
+const MAX_POINTS: i32 = 10000;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Point {
+	pub x: f64,
+	pub f: Option<f64>,
+	pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Results {
+	pub a: f64,
+	pub b: f64,
+	pub n: i32,
+	pub spacing: String,
+	pub points: Vec<Point>,
+}
+
+pub fn raw(
+	a_str: &RawStr,
+	b_str: &RawStr,
+	n_str: &RawStr,
+	input_str: &RawStr,
+	spacing: Option<&str>,
+) -> Result<Results, String> {
+	let spacings = ["linear", "log"];
+	let spacing = spacing.unwrap_or("linear").to_lowercase();
+	if !spacings.contains(&spacing.as_str()) {
+		return Err(format!("Error: the spacing must be one of {}, not '{}'.", spacings.join(", "), spacing));
+	}
+	let a = match helper::parse_expression(a_str.to_string()) {
+		Ok(a) => a,
+		Err(message) => return Err(message),
+	};
+	let b = match helper::parse_expression(b_str.to_string()) {
+		Ok(b) => b,
+		Err(message) => return Err(message),
+	};
+	if b <= a {
+		return Err(format!("Error: the last value of x ({}) must exceed the first one ({}).", b, a));
+	}
+	let log = spacing == "log";
+	if log && a <= 0. {
+		return Err(format!("Error: logarithmic spacing requires positive values of x, unlike {}.", a));
+	}
+	let n = match helper::parse_expression(n_str.to_string()) {
+		Ok(n) => {
+			if n.round() != n {
+				return Err(format!("{} is not an integer.", n));
+			} else if n < 2. || n > MAX_POINTS as f64 {
+				return Err(format!("Error: the number of points must be between 2 and {}, unlike {}.", MAX_POINTS, n));
+			}
+			n as i32
+		},
+		Err(message) => return Err(message),
+	};
+	let names = vec!["x".to_string()];
+	let mut points = vec![];
+	for i in 0..n {
+		let fraction = (i as f64) / ((n - 1) as f64);
+		let x = if i == n - 1 {b} else if log {10_f64.powf(a.log10() + fraction * (b.log10() - a.log10()))} else {a + fraction * (b - a)};
+		// An error at one point is reported there, rather than for the whole table.
+		points.push(match helper::function_n(input_str, &names, &[x]) {
+			Ok(f) => {
+				if f.is_finite() {
+					Point {x, f: Some(f), error: None}
+				} else {
+					Point {x, f: None, error: Some(format!("Error: the value of the function at x = {} is {}.", x, f))}
+				}
+			},
+			Err(message) => Point {x, f: None, error: Some(message)},
+		});
+	}
+	Ok(Results {a, b, n, spacing, points})
+}
+
+// Formats the table as comma-separated values, quoting each error message because it may contain commas.
+pub fn csv(results: &Results) -> String {
+	let mut lines = vec!["x,f,error".to_string()];
+	for point in &results.points {
+		lines.push(format!(
+			"{},{},{}",
+			point.x,
+			point.f.map_or("".to_string(), |f| f.to_string()),
+			point.error.as_ref().map_or("".to_string(), |error| format!("\"{}\"", str::replace(error, "\"", "\"\""))),
+		));
+	}
+	format!("{}\n", lines.join("\n"))
+}